@@ -0,0 +1,42 @@
+create table public.game
+(
+    id          bigserial
+        constraint game_pk
+            primary key,
+    public_id   uuid    not null,
+    lobby_id    bigint  not null
+        constraint game_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    topic       text    not null,
+    words       text[]  not null,
+    secret_word integer not null
+);
+
+create unique index game_public_id_uindex
+    on public.game (public_id);
+
+create index game_lobby_id_index
+    on public.game (lobby_id);
+
+create table public.game_player
+(
+    id        bigserial
+        constraint game_player_pk
+            primary key,
+    game_id   bigint  not null
+        constraint game_player_game_id_fk
+            references public.game
+            on delete cascade,
+    user_id   bigint  not null
+        constraint game_player_user_id_fk
+            references public."user"
+            on delete cascade,
+    chameleon boolean not null
+);
+
+create index game_player_game_id_index
+    on public.game_player (game_id);
+
+create unique index game_player_user_id_game_id_uindex
+    on public.game_player (user_id, game_id);
@@ -0,0 +1,5 @@
+alter table public.game
+    add phase text default 'clues' not null;
+
+alter table public.game_player
+    add clue text;
@@ -0,0 +1,20 @@
+alter table public.lobby
+    add tie_break text default 'host' not null;
+
+alter table public.game
+    add tie_break text default 'host' not null;
+
+alter table public.game
+    add accused_id bigint
+        constraint game_accused_id_fk
+            references public."user"
+            on delete set null;
+
+alter table public.game_player
+    add candidate bool default TRUE not null;
+
+alter table public.game_player
+    add vote_id bigint
+        constraint game_player_vote_id_fk
+            references public."user"
+            on delete set null;
@@ -0,0 +1,21 @@
+alter table public.game
+    add guess text;
+
+create table public.lobby_score
+(
+    id       bigserial
+        constraint lobby_score_pk
+            primary key,
+    lobby_id bigint            not null
+        constraint lobby_score_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    user_id  bigint            not null
+        constraint lobby_score_user_id_fk
+            references public."user"
+            on delete cascade,
+    score    integer default 0 not null
+);
+
+create unique index lobby_score_lobby_id_user_id_uindex
+    on public.lobby_score (lobby_id, user_id);
@@ -0,0 +1,2 @@
+alter table public.lobby
+    add drawn_topics text[] default '{}' not null;
@@ -0,0 +1,40 @@
+create table public.deck
+(
+    id        bigserial
+        constraint deck_pk
+            primary key,
+    public_id uuid   not null,
+    owner_id  bigint not null
+        constraint deck_owner_id_fk
+            references public."user"
+            on delete cascade,
+    name      text   not null
+);
+
+create unique index deck_public_id_uindex
+    on public.deck (public_id);
+
+create index deck_owner_id_index
+    on public.deck (owner_id);
+
+create table public.deck_topic
+(
+    id      bigserial
+        constraint deck_topic_pk
+            primary key,
+    deck_id bigint not null
+        constraint deck_topic_deck_id_fk
+            references public.deck
+            on delete cascade,
+    title   text   not null,
+    words   text[] not null
+);
+
+create index deck_topic_deck_id_index
+    on public.deck_topic (deck_id);
+
+alter table public.lobby
+    add deck_id bigint
+        constraint lobby_deck_id_fk
+            references public.deck
+            on delete set null;
@@ -0,0 +1,8 @@
+alter table public.game
+    add seed bigint;
+
+alter table public.game
+    add roll_d6 smallint;
+
+alter table public.game
+    add roll_d8 smallint;
@@ -0,0 +1,61 @@
+alter table public.lobby
+    add rounds integer default 3;
+
+alter table public.lobby
+    add target_score integer;
+
+alter table public.lobby
+    add constraint lobby_goal_check
+        check (num_nonnulls(rounds, target_score) = 1);
+
+create table public.lobby_match
+(
+    id            bigserial
+        constraint lobby_match_pk
+            primary key,
+    public_id     uuid                  not null,
+    lobby_id      bigint                not null
+        constraint lobby_match_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    rounds        integer,
+    target_score  integer,
+    rounds_played integer default 0     not null,
+    finished      boolean default FALSE not null,
+    constraint lobby_match_goal_check
+        check (num_nonnulls(rounds, target_score) = 1)
+);
+
+create unique index lobby_match_public_id_uindex
+    on public.lobby_match (public_id);
+
+create index lobby_match_lobby_id_index
+    on public.lobby_match (lobby_id);
+
+create table public.lobby_match_standing
+(
+    id             bigserial
+        constraint lobby_match_standing_pk
+            primary key,
+    lobby_match_id bigint  not null
+        constraint lobby_match_standing_lobby_match_id_fk
+            references public.lobby_match
+            on delete cascade,
+    user_id        bigint  not null
+        constraint lobby_match_standing_user_id_fk
+            references public."user"
+            on delete cascade,
+    score          integer not null
+);
+
+create index lobby_match_standing_lobby_match_id_index
+    on public.lobby_match_standing (lobby_match_id);
+
+alter table public.game
+    add lobby_match_id bigint
+        constraint game_lobby_match_id_fk
+            references public.lobby_match
+            on delete set null;
+
+alter table public.game
+    add round integer;
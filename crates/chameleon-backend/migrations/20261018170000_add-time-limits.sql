@@ -0,0 +1,17 @@
+alter table public.lobby
+    add clue_time_limit integer default 0 not null;
+
+alter table public.lobby
+    add vote_time_limit integer default 0 not null;
+
+alter table public.game
+    add clue_time_limit integer default 0 not null;
+
+alter table public.game
+    add vote_time_limit integer default 0 not null;
+
+alter table public.game
+    add deadline timestamp with time zone;
+
+alter table public.game_player
+    add skipped boolean default FALSE not null;
@@ -0,0 +1,20 @@
+create table public.lobby_ban
+(
+    id       bigserial
+        constraint lobby_ban_pk
+            primary key,
+    lobby_id bigint not null
+        constraint lobby_ban_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    user_id  bigint not null
+        constraint lobby_ban_user_id_fk
+            references public."user"
+            on delete cascade
+);
+
+create index lobby_ban_lobby_id_index
+    on public.lobby_ban (lobby_id);
+
+create unique index lobby_ban_user_id_lobby_id_uindex
+    on public.lobby_ban (user_id, lobby_id);
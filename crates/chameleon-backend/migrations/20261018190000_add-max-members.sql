@@ -0,0 +1,2 @@
+alter table public.lobby
+    add max_members integer default 8 not null;
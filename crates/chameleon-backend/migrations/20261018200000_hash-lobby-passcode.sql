@@ -0,0 +1,5 @@
+create extension if not exists pgcrypto;
+
+update public.lobby
+set passcode = crypt(passcode, gen_salt('bf', 10))
+where passcode is not null;
@@ -0,0 +1,5 @@
+alter table public.lobby
+    add created_at timestamptz default now() not null;
+
+create index lobby_created_at_index
+    on public.lobby (created_at);
@@ -0,0 +1,2 @@
+alter table public.lobby
+    add visibility text default 'public' not null;
@@ -0,0 +1,39 @@
+create table public.lobby_invite
+(
+    id         bigserial
+        constraint lobby_invite_pk
+            primary key,
+    public_id  uuid                  not null,
+    lobby_id   bigint                not null
+        constraint lobby_invite_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    expires_at timestamptz           not null,
+    max_uses   integer               not null,
+    revoked    boolean default FALSE not null
+);
+
+create unique index lobby_invite_public_id_uindex
+    on public.lobby_invite (public_id);
+
+create index lobby_invite_lobby_id_index
+    on public.lobby_invite (lobby_id);
+
+create table public.lobby_invite_redemption
+(
+    id              bigserial
+        constraint lobby_invite_redemption_pk
+            primary key,
+    lobby_invite_id bigint                    not null
+        constraint lobby_invite_redemption_lobby_invite_id_fk
+            references public.lobby_invite
+            on delete cascade,
+    user_id         bigint                    not null
+        constraint lobby_invite_redemption_user_id_fk
+            references public."user"
+            on delete cascade,
+    redeemed_at     timestamptz default now() not null
+);
+
+create index lobby_invite_redemption_lobby_invite_id_index
+    on public.lobby_invite_redemption (lobby_invite_id);
@@ -0,0 +1,2 @@
+alter table public.lobby_member
+    add ready boolean default false not null;
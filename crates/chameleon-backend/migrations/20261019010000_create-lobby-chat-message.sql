@@ -0,0 +1,23 @@
+create table public.lobby_chat_message
+(
+    id         bigserial
+        constraint lobby_chat_message_pk
+            primary key,
+    public_id  uuid                      not null,
+    lobby_id   bigint                    not null
+        constraint lobby_chat_message_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    user_id    bigint                    not null
+        constraint lobby_chat_message_user_id_fk
+            references public."user"
+            on delete cascade,
+    message    text                      not null,
+    created_at timestamptz default now() not null
+);
+
+create unique index lobby_chat_message_public_id_uindex
+    on public.lobby_chat_message (public_id);
+
+create index lobby_chat_message_lobby_id_index
+    on public.lobby_chat_message (lobby_id);
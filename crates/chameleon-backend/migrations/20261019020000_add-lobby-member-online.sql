@@ -0,0 +1,2 @@
+alter table public.lobby_member
+    add online boolean default false not null;
@@ -0,0 +1,23 @@
+alter table public.lobby
+    add event_seq bigint default 0 not null;
+
+create table public.lobby_event
+(
+    id           bigserial
+        constraint lobby_event_pk
+            primary key,
+    lobby_id     bigint                    not null
+        constraint lobby_event_lobby_id_fk
+            references public.lobby
+            on delete cascade,
+    seq          bigint                    not null,
+    recipient_id bigint
+        constraint lobby_event_recipient_id_fk
+            references public."user"
+            on delete cascade,
+    frame        text                      not null,
+    created_at   timestamptz default now() not null
+);
+
+create unique index lobby_event_lobby_id_seq_uindex
+    on public.lobby_event (lobby_id, seq);
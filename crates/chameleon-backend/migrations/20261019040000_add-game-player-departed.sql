@@ -0,0 +1,2 @@
+alter table public.game_player
+    add departed boolean default false not null;
@@ -0,0 +1,14 @@
+create table public.lobby_member_connection
+(
+    id              bigserial
+        constraint lobby_member_connection_pk
+            primary key,
+    lobby_member_id bigint      not null
+        constraint lobby_member_connection_lobby_member_id_fk
+            references public.lobby_member
+            on delete cascade,
+    expires_at      timestamptz not null
+);
+
+create index lobby_member_connection_lobby_member_id_index
+    on public.lobby_member_connection (lobby_member_id);
@@ -6,7 +6,12 @@ use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
 
 use crate::{
     args::Args,
-    routes::{api_v1_lobbies, api_v1_ping, api_v1_userinfo, api_v1_users, ws_v1_lobbies},
+    hub::Hub,
+    presence::Presence,
+    routes::{
+        api_v1_decks, api_v1_lobbies, api_v1_ping, api_v1_userinfo, api_v1_users, ws_v1_lobbies,
+    },
+    timer,
 };
 
 #[allow(clippy::missing_panics_doc)]
@@ -23,12 +28,26 @@ pub async fn app() {
         .await
         .expect("Failed to migrate postgres database");
 
+    timer::restore(&postgres_pool)
+        .await
+        .expect("Failed to restore game deadlines");
+
+    let presence = Presence::new(postgres_pool.clone());
+
+    presence
+        .restore()
+        .await
+        .expect("Failed to restore member presence");
+
     let state = AppState {
+        hub: Hub::spawn(postgres_pool.clone()),
         pool: postgres_pool,
+        presence,
     };
 
     let app = Router::new()
         .merge(SpaRouter::new("/assets", "dist"))
+        .nest(api_v1_decks::PATH, api_v1_decks::router())
         .nest(api_v1_lobbies::PATH, api_v1_lobbies::router())
         .nest(api_v1_ping::PATH, api_v1_ping::router())
         .nest(api_v1_users::PATH, api_v1_users::router())
@@ -45,5 +64,7 @@ pub async fn app() {
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone)]
 pub struct AppState {
+    pub hub: Hub,
     pub pool: Pool<Postgres>,
+    pub presence: Presence,
 }
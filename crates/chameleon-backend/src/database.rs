@@ -1,10 +1,24 @@
+use std::time::Duration;
+
 use chameleon_protocol::{
+    attributes,
     frames::{self, LobbyFrame, LobbyRequest},
     jsonapi::{self, Source},
+    jsonrpc::FrameType,
+    topic,
+};
+use sqlx::{
+    postgres::PgListener, Executor, PgConnection, Pool, Postgres, QueryBuilder, Transaction,
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::domain::{
+    chat_message_id, code_card, deck, deck_id, game, game_id, invite_id, lobby, lobby_id,
+    lobby_match, local_id, match_id, passcode, user, user_id,
 };
-use sqlx::{postgres::PgListener, Executor, Pool, Postgres};
 
-use crate::domain::{lobby, lobby_id, local_id, user, user_id};
+/// Frames kept per lobby for replay, a client missing more resynchronizes from the resources.
+const LOBBY_EVENT_RETENTION: i64 = 1000;
 
 pub struct Database {}
 
@@ -13,78 +27,534 @@ impl Database {
         PgListener::connect_with(conn).await
     }
 
-    pub async fn query_lobby<'c, E>(
+    pub async fn query_deck<'c, E>(
         conn: E,
         keyset_pagination: KeysetPagination,
-    ) -> Result<(Vec<lobby::Query>, i64), sqlx::Error>
+    ) -> Result<Page<deck::Query>, sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         let records = sqlx::query!(
-            r#"SELECT l.id, l.public_id, l.name, l.require_passcode
-            FROM lobby l
-            WHERE l.id > $1
-            ORDER BY l.id
-            LIMIT $2;"#,
-            keyset_pagination.id,
-            keyset_pagination.limit,
+            r#"SELECT d.id, d.public_id, d.name, u.public_id owner_public_id
+            FROM deck d
+                     JOIN "user" u ON u.id = d.owner_id
+            WHERE d.id > $1
+              AND d.id < $2
+            ORDER BY CASE WHEN $3 THEN d.id END DESC, d.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
         )
         .fetch_all(conn)
         .await?;
 
-        let last_record_id = records
-            .last()
-            .map_or(keyset_pagination.id, |record| record.id);
+        let page = keyset_pagination
+            .page(records, |record| record.id)
+            .map(|record| deck::Query {
+                id: deck_id::DeckId(record.public_id),
+                name: record.name,
+                owner: user_id::UserId(record.owner_public_id),
+            });
+
+        Ok(page)
+    }
+
+    /// Query the pending deadlines of the latest game of each lobby.
+    pub async fn query_game_deadline<'c, E>(
+        conn: E,
+    ) -> Result<Vec<(lobby_id::LobbyId, game_id::GameId, OffsetDateTime)>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT DISTINCT ON (g.lobby_id) l.public_id lobby_public_id,
+                                             g.public_id,
+                                             g.deadline
+            FROM game g
+                     JOIN lobby l ON l.id = g.lobby_id
+            ORDER BY g.lobby_id, g.id DESC;"#
+        )
+        .fetch_all(conn)
+        .await
+        .map(|records| {
+            records
+                .into_iter()
+                .filter_map(|record| {
+                    record.deadline.map(|deadline| {
+                        (
+                            lobby_id::LobbyId(record.lobby_public_id),
+                            game_id::GameId(record.public_id),
+                            deadline,
+                        )
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Query public lobbies in the order of the sort, ties are ordered by id.
+    pub async fn query_lobby<'c, E>(
+        conn: E,
+        filter: &LobbyFilter,
+        sort: &LobbySort,
+        lobby_pagination: &LobbyPagination,
+    ) -> Result<Page<lobby::Query, LobbyCursor>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        #[derive(sqlx::FromRow)]
+        struct Record {
+            id: i64,
+            public_id: uuid::Uuid,
+            name: String,
+            require_passcode: bool,
+            max_members: i32,
+            created_at: OffsetDateTime,
+            member_count: i64,
+        }
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT l.id,
+                   l.public_id,
+                   l.name,
+                   l.require_passcode,
+                   l.max_members,
+                   l.created_at,
+                   l.member_count
+            FROM (SELECT l.*,
+                         (SELECT COUNT(*) FROM lobby_member lm WHERE lm.lobby_id = l.id) member_count
+                  FROM lobby l) l
+            WHERE l.visibility = 'public'",
+        );
+
+        if let Some(name) = &filter.name {
+            builder
+                .push(" AND STRPOS(LOWER(l.name), LOWER(")
+                .push_bind(name.clone())
+                .push(")) > 0");
+        }
+
+        if let Some(require_passcode) = filter.require_passcode {
+            builder
+                .push(" AND l.require_passcode = ")
+                .push_bind(require_passcode);
+        }
+
+        if let Some(has_space) = filter.has_space {
+            builder
+                .push(" AND (l.member_count < l.max_members) = ")
+                .push_bind(has_space);
+        }
+
+        // the unique id makes the order total, so pages neither skip nor repeat lobbies on ties
+        let keys = sort
+            .0
+            .iter()
+            .copied()
+            .chain(std::iter::once((LobbySortKey::Id, false)))
+            .collect::<Vec<_>>();
+
+        if let Some(after) = &lobby_pagination.after {
+            after.push_keyset(&keys, false, &mut builder);
+        }
+
+        if let Some(before) = &lobby_pagination.before {
+            before.push_keyset(&keys, true, &mut builder);
+        }
+
+        // backwards the order is reversed, the page is put back in order afterwards
+        let backward = lobby_pagination.is_backward();
+
+        builder.push(" ORDER BY ");
+        let mut separated = builder.separated(", ");
+        for (key, descending) in &keys {
+            separated.push(format!(
+                "{} {}",
+                key.column(),
+                if *descending == backward {
+                    "ASC"
+                } else {
+                    "DESC"
+                }
+            ));
+        }
+
+        builder
+            .push(" LIMIT ")
+            .push_bind(lobby_pagination.fetch_limit())
+            .push(";");
 
-        let lobbies = records
-            .into_iter()
+        let records = builder.build_query_as::<Record>().fetch_all(conn).await?;
+
+        let page = lobby_pagination
+            .page(records, |record| LobbyCursor {
+                id: record.id,
+                created_at: record.created_at,
+                member_count: record.member_count,
+                name: record.name.clone(),
+            })
             .map(|record| lobby::Query {
                 id: lobby_id::LobbyId(record.public_id),
+                max_members: u32::try_from(record.max_members).unwrap(),
+                member_count: u32::try_from(record.member_count).unwrap(),
                 name: record.name,
                 require_passcode: record.require_passcode,
-            })
-            .collect();
+            });
+
+        Ok(page)
+    }
+
+    pub async fn query_lobby_ban<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        keyset_pagination: KeysetPagination,
+    ) -> Result<Page<user::User>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let records = sqlx::query!(
+            r#"SELECT lb.id, u.public_id, u.name
+            FROM lobby l
+                     JOIN lobby_ban lb on l.id = lb.lobby_id
+                     JOIN "user" u on u.id = lb.user_id
+            WHERE l.public_id = $5
+              AND lb.id > $1
+              AND lb.id < $2
+            ORDER BY CASE WHEN $3 THEN lb.id END DESC, lb.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
+            lobby_id.0
+        )
+        .fetch_all(conn)
+        .await?;
 
-        Ok((lobbies, last_record_id))
+        let page = keyset_pagination
+            .page(records, |record| record.id)
+            .map(|record| user::User {
+                id: user_id::UserId(record.public_id),
+                name: record.name,
+            });
+
+        Ok(page)
+    }
+
+    pub async fn query_lobby_chat_message<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        keyset_pagination: KeysetPagination,
+    ) -> Result<Page<lobby::ChatMessage>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let records = sqlx::query!(
+            r#"SELECT lcm.id, lcm.public_id, lcm.created_at, lcm.message, u.public_id user_public_id
+            FROM lobby l
+                     JOIN lobby_chat_message lcm on l.id = lcm.lobby_id
+                     JOIN "user" u on u.id = lcm.user_id
+            WHERE l.public_id = $5
+              AND lcm.id > $1
+              AND lcm.id < $2
+            ORDER BY CASE WHEN $3 THEN lcm.id END DESC, lcm.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
+            lobby_id.0
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let page = keyset_pagination
+            .page(records, |record| record.id)
+            .map(|record| lobby::ChatMessage {
+                id: chat_message_id::ChatMessageId(record.public_id),
+                created_at: record.created_at,
+                message: record.message,
+                user_id: user_id::UserId(record.user_public_id),
+            });
+
+        Ok(page)
+    }
+
+    pub async fn query_lobby_invite<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        keyset_pagination: KeysetPagination,
+    ) -> Result<Page<lobby::Invite>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let records = sqlx::query!(
+            r#"SELECT li.id,
+                   li.public_id,
+                   li.expires_at,
+                   li.max_uses,
+                   li.revoked,
+                   (SELECT COUNT(*) FROM lobby_invite_redemption lir WHERE lir.lobby_invite_id = li.id) "uses!"
+            FROM lobby l
+                     JOIN lobby_invite li on l.id = li.lobby_id
+            WHERE l.public_id = $5
+              AND li.id > $1
+              AND li.id < $2
+            ORDER BY CASE WHEN $3 THEN li.id END DESC, li.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
+            lobby_id.0
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let page = keyset_pagination
+            .page(records, |record| record.id)
+            .map(|record| lobby::Invite {
+                id: invite_id::InviteId(record.public_id),
+                expires_at: record.expires_at,
+                max_uses: u32::try_from(record.max_uses).unwrap(),
+                revoked: record.revoked,
+                uses: u32::try_from(record.uses).unwrap(),
+            });
+
+        Ok(page)
+    }
+
+    pub async fn query_lobby_match<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        keyset_pagination: KeysetPagination,
+    ) -> Result<Page<lobby_match::Match>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres> + Copy,
+    {
+        let records = sqlx::query!(
+            r#"SELECT lm.id, lm.public_id, lm.rounds, lm.target_score, lm.rounds_played, lm.finished
+            FROM lobby l
+                     JOIN lobby_match lm on l.id = lm.lobby_id
+            WHERE l.public_id = $5
+              AND lm.id > $1
+              AND lm.id < $2
+            ORDER BY CASE WHEN $3 THEN lm.id END DESC, lm.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
+            lobby_id.0
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let page = keyset_pagination.page(records, |record| record.id);
+
+        let standings = sqlx::query!(
+            r#"SELECT lms.lobby_match_id, u.public_id, lms.score
+            FROM lobby_match_standing lms
+                     JOIN "user" u on u.id = lms.user_id
+            WHERE lms.lobby_match_id = ANY ($1)
+            ORDER BY lms.score DESC, lms.id;"#,
+            &page
+                .items
+                .iter()
+                .map(|record| record.id)
+                .collect::<Vec<_>>()
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let page = page.map(|record| lobby_match::Match {
+            id: match_id::MatchId(record.public_id),
+            finished: record.finished,
+            goal: goal(record.rounds, record.target_score),
+            rounds_played: u32::try_from(record.rounds_played).unwrap(),
+            scores: standings
+                .iter()
+                .filter(|standing| standing.lobby_match_id == record.id)
+                .map(|standing| lobby::Score {
+                    user_id: user_id::UserId(standing.public_id),
+                    score: u32::try_from(standing.score).unwrap(),
+                })
+                .collect(),
+        });
+
+        Ok(page)
     }
 
     pub async fn query_lobby_member<'c, E>(
         conn: E,
         lobby_id: lobby_id::LobbyId,
         keyset_pagination: KeysetPagination,
-    ) -> Result<(Vec<user::User>, i64), sqlx::Error>
+    ) -> Result<Page<lobby::MemberQuery>, sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         let records = sqlx::query!(
-            r#"SELECT lm.id, u.public_id, u.name
+            r#"SELECT lm.id, u.public_id, u.name, lm.online, lm.ready
             FROM lobby l
                      JOIN lobby_member lm on l.id = lm.lobby_id
                      JOIN "user" u on u.id = lm.user_id
-            WHERE l.public_id = $3
+            WHERE l.public_id = $5
               AND lm.id > $1
-            ORDER BY lm.id
-            LIMIT $2;"#,
-            keyset_pagination.id,
-            keyset_pagination.limit,
+              AND lm.id < $2
+            ORDER BY CASE WHEN $3 THEN lm.id END DESC, lm.id
+            LIMIT $4;"#,
+            keyset_pagination.lower(),
+            keyset_pagination.upper(),
+            keyset_pagination.is_backward(),
+            keyset_pagination.fetch_limit(),
             lobby_id.0
         )
         .fetch_all(conn)
         .await?;
 
-        let last_record_id = records
-            .last()
-            .map_or(keyset_pagination.id, |record| record.id);
-
-        let users = records
-            .into_iter()
-            .map(|record| user::User {
+        let page = keyset_pagination
+            .page(records, |record| record.id)
+            .map(|record| lobby::MemberQuery {
                 id: user_id::UserId(record.public_id),
                 name: record.name,
-            })
-            .collect();
+                online: record.online,
+                ready: record.ready,
+            });
+
+        Ok(page)
+    }
+
+    /// Frames of the lobby after the sequence number, oldest first.
+    pub async fn query_lobby_event<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        after_seq: i64,
+    ) -> Result<Vec<LobbyNotification>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT le.frame, u.public_id "recipient?"
+            FROM lobby_event le
+                     JOIN lobby l ON l.id = le.lobby_id
+                     LEFT JOIN "user" u ON u.id = le.recipient_id
+            WHERE l.public_id = $1
+              AND le.seq > $2
+            ORDER BY le.seq;"#,
+            lobby_id.0,
+            after_seq
+        )
+        .fetch_all(conn)
+        .await
+        .map(|records| {
+            records
+                .into_iter()
+                .map(|record| LobbyNotification {
+                    recipient: record.recipient.map(user_id::UserId),
+                    frame: LobbyFrame::try_from_str(&record.frame).unwrap(),
+                })
+                .collect()
+        })
+    }
+
+    /// Sequence number of the last frame of the lobby.
+    pub async fn query_lobby_event_seq<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<i64, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query_scalar!(
+            r#"SELECT event_seq
+            FROM lobby
+            WHERE public_id = $1;"#,
+            lobby_id.0
+        )
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Whether the member holds a connection lease, live or lapsed less than `grace` ago.
+    pub async fn query_lobby_member_connected<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+        grace: Duration,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT
+                             FROM lobby_member_connection lmc
+                                      JOIN lobby_member lm ON lm.id = lmc.lobby_member_id
+                                      JOIN lobby l ON l.id = lm.lobby_id
+                                      JOIN "user" u ON u.id = lm.user_id
+                             WHERE l.public_id = $1
+                               AND u.public_id = $2
+                               AND lmc.expires_at + make_interval(secs => $3) > now()) "exists!";"#,
+            lobby_id.0,
+            user_id.0,
+            grace.as_secs_f64()
+        )
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Members marked online, to be checked against their connection leases.
+    pub async fn query_lobby_member_online<'c, E>(
+        conn: E,
+    ) -> Result<Vec<(lobby_id::LobbyId, user_id::UserId)>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT l.public_id lobby_public_id, u.public_id
+            FROM lobby_member lm
+                     JOIN lobby l ON l.id = lm.lobby_id
+                     JOIN "user" u ON u.id = lm.user_id
+            WHERE lm.online;"#
+        )
+        .fetch_all(conn)
+        .await
+        .map(|records| {
+            records
+                .into_iter()
+                .map(|record| {
+                    (
+                        lobby_id::LobbyId(record.lobby_public_id),
+                        user_id::UserId(record.public_id),
+                    )
+                })
+                .collect()
+        })
+    }
 
-        Ok((users, last_record_id))
+    pub async fn query_lobby_score<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Vec<lobby::Score>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT u.public_id, ls.score
+            FROM lobby l
+                     JOIN lobby_score ls on l.id = ls.lobby_id
+                     JOIN "user" u on u.id = ls.user_id
+            WHERE l.public_id = $1
+            ORDER BY ls.score DESC, ls.id;"#,
+            lobby_id.0
+        )
+        .map(|record| lobby::Score {
+            user_id: user_id::UserId(record.public_id),
+            score: u32::try_from(record.score).unwrap(),
+        })
+        .fetch_all(conn)
+        .await
     }
 
     pub async fn select_user_id_by_local_id<'c, E>(
@@ -106,52 +576,60 @@ impl Database {
         .await
     }
 
-    pub async fn load_lobby<'c, E>(
-        conn: E,
+    pub async fn load_deck(
+        pool: &Pool<Postgres>,
+        deck_id: deck_id::DeckId,
+    ) -> Result<Option<deck::Deck>, sqlx::Error> {
+        Self::select_deck(&mut *pool.acquire().await?, deck_id).await
+    }
+
+    /// Load within the transaction of [`Self::lock`], keeping to its connection.
+    pub async fn load_deck_locked(
+        transaction: &mut Transaction<'_, Postgres>,
+        deck_id: deck_id::DeckId,
+    ) -> Result<Option<deck::Deck>, sqlx::Error> {
+        Self::select_deck(transaction, deck_id).await
+    }
+
+    pub async fn load_game(
+        pool: &Pool<Postgres>,
         lobby_id: lobby_id::LobbyId,
-    ) -> Result<Option<lobby::Lobby>, sqlx::Error>
-    where
-        E: Executor<'c, Database = Postgres> + Copy,
-    {
-        let Some(lobby) = sqlx::query!(
-            r#"SELECT l.public_id, l.name, l.passcode, l.require_passcode, u.public_id host_public_id
-            FROM lobby l
-                     JOIN lobby_member lm ON l.id = lm.lobby_id
-                     JOIN "user" u ON u.id = lm.user_id
-            WHERE l.public_id = $1
-              AND lm.host IS TRUE;"#,
-            lobby_id.0
-        )
-        .fetch_optional(conn)
-        .await? else {
-            return Ok(None);
-        };
+    ) -> Result<Option<game::Game>, sqlx::Error> {
+        Self::select_game(&mut *pool.acquire().await?, lobby_id).await
+    }
 
-        let members = sqlx::query!(
-            r#"SELECT u.public_id, lm.host
-            FROM lobby_member lm
-                     JOIN "user" u on lm.user_id = u.id
-                     JOIN lobby l on lm.lobby_id = l.id
-            WHERE l.public_id = $1;
-            "#,
-            lobby_id.0
-        )
-        .fetch_all(conn)
-        .await?;
+    /// Load under the lobby row lock of the transaction, see [`Self::lock`].
+    pub async fn load_game_locked(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<game::Game>, sqlx::Error> {
+        Self::select_game(transaction, lobby_id).await
+    }
 
-        Ok(Some(lobby::Lobby {
-            id: lobby_id::LobbyId(lobby.public_id),
-            name: lobby.name,
-            members: members
-                .into_iter()
-                .map(|member| lobby::Member {
-                    host: member.host,
-                    user_id: user_id::UserId(member.public_id),
-                })
-                .collect(),
-            passcode: lobby.passcode,
-            require_passcode: lobby.require_passcode,
-        }))
+    pub async fn load_lobby(
+        pool: &Pool<Postgres>,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<lobby::Lobby>, sqlx::Error> {
+        Self::select_lobby(&mut *pool.acquire().await?, lobby_id).await
+    }
+
+    /// Load under the lobby row lock of the transaction, see [`Self::lock`].
+    pub async fn load_lobby_locked(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<lobby::Lobby>, sqlx::Error> {
+        Self::select_lobby(transaction, lobby_id).await
+    }
+
+    /// Load the latest match of the lobby.
+    ///
+    /// Scores of an unfinished match are the running lobby scores. Loaded
+    /// under the lobby row lock of the transaction, see [`Self::lock`].
+    pub async fn load_match_locked(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<lobby_match::Match>, sqlx::Error> {
+        Self::select_match(transaction, lobby_id).await
     }
 
     pub async fn load_user<'c, E>(
@@ -177,20 +655,351 @@ impl Database {
         })
     }
 
-    pub async fn save_lobby(
+    /// Begin a transaction holding the lobby row lock, what is loaded through it stays current
+    /// until the events applied through it commit.
+    pub async fn lock(
         pool: &Pool<Postgres>,
         lobby_id: lobby_id::LobbyId,
-        events: &[lobby::Events],
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
         let mut transaction = pool.begin().await?;
+        Self::lock_lobby(&mut transaction, lobby_id).await?;
+        Ok(transaction)
+    }
 
-        for event in events {
+    pub async fn save_deck(
+        pool: &Pool<Postgres>,
+        deck_id: deck_id::DeckId,
+        events: &[deck::Events],
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+
+        for event in events {
+            match event {
+                deck::Events::Created(event) => {
+                    Self::insert_deck(&mut transaction, deck_id, event.owner, &event.name).await?;
+                    for topic in &event.topics {
+                        Self::insert_deck_topic(&mut transaction, deck_id, topic).await?;
+                    }
+                }
+                deck::Events::Deleted => {
+                    Self::delete_deck(&mut transaction, deck_id).await?;
+                }
+                deck::Events::Updated(event) => {
+                    Self::update_deck(&mut transaction, deck_id, &event.name).await?;
+                    Self::delete_deck_topics(&mut transaction, deck_id).await?;
+                    for topic in &event.topics {
+                        Self::insert_deck_topic(&mut transaction, deck_id, topic).await?;
+                    }
+                }
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn save_lobby(
+        pool: &Pool<Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        events: &[lobby::Events],
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        Self::lock_lobby(&mut transaction, lobby_id).await?;
+        Self::apply_lobby_events(&mut transaction, lobby_id, events).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn save_user(
+        pool: &Pool<Postgres>,
+        user_id: user_id::UserId,
+        events: &[user::Events],
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+
+        for event in events {
+            match event {
+                user::Events::Created(event) => {
+                    Self::insert_user(&mut transaction, user_id, &event.name).await?;
+                }
+                user::Events::Linked(local_id) => {
+                    Self::insert_local(&mut transaction, *local_id, user_id).await?;
+                }
+                user::Events::Updated(event) => {
+                    Self::update_user(&mut transaction, user_id, &event.name).await?;
+                }
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Take a connection lease for a socket of the member, dropping their lapsed ones.
+    pub async fn insert_lobby_member_connection<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+        lease: Duration,
+    ) -> Result<i64, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query_scalar!(
+            r#"WITH member AS (SELECT lm.id
+                               FROM lobby_member lm
+                                        JOIN lobby l ON l.id = lm.lobby_id
+                                        JOIN "user" u ON u.id = lm.user_id
+                               WHERE l.public_id = $1
+                                 AND u.public_id = $2),
+                    lapsed AS (DELETE
+                               FROM lobby_member_connection
+                               WHERE lobby_member_id = (SELECT id FROM member)
+                                 AND expires_at <= now())
+            INSERT
+            INTO lobby_member_connection (lobby_member_id, expires_at)
+            VALUES ((SELECT id FROM member), now() + make_interval(secs => $3))
+            RETURNING id;"#,
+            lobby_id.0,
+            user_id.0,
+            lease.as_secs_f64()
+        )
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Extend a connection lease from now, a zero lease ends it.
+    pub async fn update_lobby_member_connection<'c, E>(
+        conn: E,
+        connection_id: i64,
+        lease: Duration,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE lobby_member_connection
+            SET expires_at = now() + make_interval(secs => $2)
+            WHERE id = $1;"#,
+            connection_id,
+            lease.as_secs_f64()
+        )
+        .execute(conn)
+        .await
+        .map(|_| ())
+    }
+
+    #[allow(clippy::too_many_lines)] // reason = "one arm per game event"
+    pub async fn apply_game_events(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        game_id: game_id::GameId,
+        events: &[game::Events],
+    ) -> Result<(), sqlx::Error> {
+        for event in events {
+            match event {
+                game::Events::Accused(user_id) => {
+                    Self::update_game_accused(&mut *transaction, game_id, *user_id).await?;
+                }
+                game::Events::ClueSkipped(user_id) => {
+                    Self::update_game_player_skipped(&mut *transaction, game_id, *user_id).await?;
+                }
+                game::Events::ClueSubmitted(event) => {
+                    Self::update_game_player_clue(
+                        &mut *transaction,
+                        game_id,
+                        event.user_id,
+                        &event.clue,
+                    )
+                    .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::ClueSubmitted(frames::LobbyClueSubmitted {
+                            game_id: Some(game_id.0.to_string()),
+                            user_id: Some(event.user_id.0.to_string()),
+                            clue: Some(event.clue.clone()),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::Dealt(event) => {
+                    Self::insert_game_player(
+                        &mut *transaction,
+                        game_id,
+                        event.user_id,
+                        event.chameleon,
+                    )
+                    .await?;
+                    Self::notify_lobby_user(
+                        &mut *transaction,
+                        lobby_id,
+                        event.user_id,
+                        frames::LobbyRequest::GameDealt(frames::LobbyGameDealt {
+                            game_id: Some(game_id.0.to_string()),
+                            chameleon: Some(event.chameleon),
+                            secret_word: event.secret_word.clone(),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::DeadlineChanged(event) => {
+                    Self::update_game_deadline(&mut *transaction, game_id, event.deadline).await?;
+                    if let Some(deadline) = event.deadline {
+                        Self::notify_lobby(
+                            &mut *transaction,
+                            lobby_id,
+                            frames::LobbyRequest::PhaseDeadline(frames::LobbyPhaseDeadline {
+                                game_id: Some(game_id.0.to_string()),
+                                phase: Some(event.phase.as_str().to_string()),
+                                turn: event.turn.map(|user_id| user_id.0.to_string()),
+                                deadline: Some(deadline.format(&Rfc3339).unwrap()),
+                            }),
+                        )
+                        .await?;
+                    }
+                }
+                game::Events::Departed(user_id) => {
+                    Self::update_game_player_departed(&mut *transaction, game_id, *user_id).await?;
+                    Self::clear_game_player_votes_against(&mut *transaction, game_id, *user_id)
+                        .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::GamePlayerDeparted(frames::LobbyGamePlayerDeparted {
+                            game_id: Some(game_id.0.to_string()),
+                            user_id: Some(user_id.0.to_string()),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::Guessed(word) => {
+                    Self::update_game_guess(&mut *transaction, game_id, word).await?;
+                }
+                game::Events::PhaseChanged(phase) => {
+                    Self::update_game_phase(&mut *transaction, game_id, *phase).await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::GamePhaseChanged(frames::LobbyGamePhaseChanged {
+                            game_id: Some(game_id.0.to_string()),
+                            phase: Some(phase.as_str().to_string()),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::Scored(event) => {
+                    for (user_id, points) in &event.points {
+                        Self::insert_lobby_score(&mut *transaction, lobby_id, *user_id, *points)
+                            .await?;
+                    }
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::RoundScored(frames::LobbyRoundScored {
+                            game_id: Some(game_id.0.to_string()),
+                            outcome: Some(event.outcome.as_str().to_string()),
+                            points: Some(
+                                event
+                                    .points
+                                    .iter()
+                                    .map(|(user_id, points)| (user_id.0.to_string(), *points))
+                                    .collect(),
+                            ),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::Started(event) => {
+                    Self::insert_game(&mut *transaction, game_id, event).await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::GameStarted(frames::LobbyGameStarted {
+                            game_id: Some(game_id.0.to_string()),
+                            topic: Some(event.topic.title.clone()),
+                            words: Some(event.topic.words().cloned().collect()),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::Tied(user_ids) => {
+                    Self::update_game_player_candidates(&mut *transaction, game_id, user_ids)
+                        .await?;
+                }
+                game::Events::VoteCast(event) => {
+                    Self::update_game_player_vote(
+                        &mut *transaction,
+                        game_id,
+                        event.user_id,
+                        Some(event.accused),
+                    )
+                    .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::VoteCast(frames::LobbyVoteCast {
+                            game_id: Some(game_id.0.to_string()),
+                            user_id: Some(event.user_id.0.to_string()),
+                        }),
+                    )
+                    .await?;
+                }
+                game::Events::VotesCleared => {
+                    Self::clear_game_player_votes(&mut *transaction, game_id).await?;
+                }
+                game::Events::VotesRevealed(event) => {
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::VotesRevealed(frames::LobbyVotesRevealed {
+                            game_id: Some(game_id.0.to_string()),
+                            votes: Some(
+                                event
+                                    .votes
+                                    .iter()
+                                    .map(|(user_id, accused)| {
+                                        (user_id.0.to_string(), accused.0.to_string())
+                                    })
+                                    .collect(),
+                            ),
+                            accused: event.accused.map(|user_id| user_id.0.to_string()),
+                            tied: Some(
+                                event
+                                    .tied
+                                    .iter()
+                                    .map(|user_id| user_id.0.to_string())
+                                    .collect(),
+                            ),
+                        }),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)] // reason = "one arm per lobby event"
+    pub async fn apply_lobby_events(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        events: &[lobby::Events],
+    ) -> Result<(), sqlx::Error> {
+        for event in events {
             match event {
+                lobby::Events::Banned(user_id) => {
+                    Self::insert_lobby_ban(&mut *transaction, lobby_id, *user_id).await?;
+                }
                 lobby::Events::ChatMessage(chat_message) => {
+                    Self::insert_lobby_chat_message(&mut *transaction, lobby_id, chat_message)
+                        .await?;
                     Self::notify_lobby(
-                        &mut transaction,
+                        &mut *transaction,
                         lobby_id,
                         frames::LobbyRequest::ChatMessage(frames::LobbyChatMessage {
+                            id: Some(chat_message.id.0.to_string()),
                             user_id: Some(chat_message.user_id.0.to_string()),
                             message: Some(chat_message.message.clone()),
                         }),
@@ -198,30 +1007,41 @@ impl Database {
                     .await?;
                 }
                 lobby::Events::Created(event) => {
-                    Self::insert_lobby(
-                        &mut transaction,
-                        lobby_id,
-                        &event.name,
-                        &event.passcode,
-                        event.require_passcode,
-                    )
-                    .await?;
+                    Self::insert_lobby(&mut *transaction, lobby_id, event).await?;
                 }
                 lobby::Events::Empty => {
-                    Self::delete_lobby(&mut transaction, lobby_id).await?;
+                    Self::delete_lobby(&mut *transaction, lobby_id).await?;
                 }
                 lobby::Events::HostGranted(user_id) => {
-                    Self::update_lobby_member_host(&mut transaction, lobby_id, *user_id, true)
+                    Self::update_lobby_member_host(&mut *transaction, lobby_id, *user_id, true)
                         .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::HostChanged(frames::LobbyHostChanged {
+                            user_id: Some(user_id.0.to_string()),
+                        }),
+                    )
+                    .await?;
                 }
                 lobby::Events::HostRevoked(user_id) => {
-                    Self::update_lobby_member_host(&mut transaction, lobby_id, *user_id, false)
+                    Self::update_lobby_member_host(&mut *transaction, lobby_id, *user_id, false)
+                        .await?;
+                }
+                lobby::Events::InviteCreated(invite) => {
+                    Self::insert_lobby_invite(&mut *transaction, lobby_id, invite).await?;
+                }
+                lobby::Events::InviteRedeemed(invite_id, user_id) => {
+                    Self::insert_lobby_invite_redemption(&mut *transaction, *invite_id, *user_id)
                         .await?;
                 }
+                lobby::Events::InviteRevoked(invite_id) => {
+                    Self::update_lobby_invite_revoked(&mut *transaction, *invite_id).await?;
+                }
                 lobby::Events::Joined(user_id) => {
-                    Self::insert_lobby_member(&mut transaction, lobby_id, *user_id).await?;
+                    Self::insert_lobby_member(&mut *transaction, lobby_id, *user_id).await?;
                     Self::notify_lobby(
-                        &mut transaction,
+                        &mut *transaction,
                         lobby_id,
                         frames::LobbyRequest::UserJoined(frames::LobbyUserJoined {
                             user_id: Some(user_id.0.to_string()),
@@ -229,10 +1049,21 @@ impl Database {
                     )
                     .await?;
                 }
+                lobby::Events::Kicked(user_id) => {
+                    Self::delete_lobby_member(&mut *transaction, lobby_id, *user_id).await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::UserKicked(frames::LobbyUserKicked {
+                            user_id: Some(user_id.0.to_string()),
+                        }),
+                    )
+                    .await?;
+                }
                 lobby::Events::Left(user_id) => {
-                    Self::delete_lobby_member(&mut transaction, lobby_id, *user_id).await?;
+                    Self::delete_lobby_member(&mut *transaction, lobby_id, *user_id).await?;
                     Self::notify_lobby(
-                        &mut transaction,
+                        &mut *transaction,
                         lobby_id,
                         frames::LobbyRequest::UserLeft(frames::LobbyUserLeft {
                             user_id: Some(user_id.0.to_string()),
@@ -240,236 +1071,1455 @@ impl Database {
                     )
                     .await?;
                 }
+                lobby::Events::PresenceChanged(user_id, online) => {
+                    Self::update_lobby_member_online(
+                        &mut *transaction,
+                        lobby_id,
+                        *user_id,
+                        *online,
+                    )
+                    .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::PresenceChanged(frames::LobbyPresenceChanged {
+                            user_id: Some(user_id.0.to_string()),
+                            online: Some(*online),
+                        }),
+                    )
+                    .await?;
+                }
+                lobby::Events::ReadyChanged(user_id, ready) => {
+                    Self::update_lobby_member_ready(&mut *transaction, lobby_id, *user_id, *ready)
+                        .await?;
+                    Self::notify_lobby(
+                        &mut *transaction,
+                        lobby_id,
+                        frames::LobbyRequest::ReadyChanged(frames::LobbyReadyChanged {
+                            user_id: Some(user_id.0.to_string()),
+                            ready: Some(*ready),
+                        }),
+                    )
+                    .await?;
+                }
+                lobby::Events::TopicDrawn(title) => {
+                    Self::update_lobby_drawn_topics_append(&mut *transaction, lobby_id, title)
+                        .await?;
+                }
+                lobby::Events::TopicsReshuffled => {
+                    Self::update_lobby_drawn_topics_clear(&mut *transaction, lobby_id).await?;
+                }
+                lobby::Events::Unbanned(user_id) => {
+                    Self::delete_lobby_ban(&mut *transaction, lobby_id, *user_id).await?;
+                }
                 lobby::Events::Updated(event) => {
-                    Self::update_lobby(
-                        &mut transaction,
+                    Self::update_lobby(&mut *transaction, lobby_id, event).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn apply_match_events(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        match_id: match_id::MatchId,
+        events: &[lobby_match::Events],
+    ) -> Result<(), sqlx::Error> {
+        for event in events {
+            match event {
+                lobby_match::Events::Finished(scores) => {
+                    Self::update_lobby_match_finished(&mut *transaction, match_id).await?;
+                    for score in scores {
+                        Self::insert_lobby_match_standing(&mut *transaction, match_id, score)
+                            .await?;
+                    }
+                    Self::notify_lobby(
+                        &mut *transaction,
                         lobby_id,
-                        &event.name,
-                        &event.passcode,
-                        event.require_passcode,
+                        frames::LobbyRequest::MatchFinished(frames::LobbyMatchFinished {
+                            match_id: Some(match_id.0.to_string()),
+                            standings: Some(
+                                scores
+                                    .iter()
+                                    .map(|score| frames::LobbyMatchStanding {
+                                        user_id: Some(score.user_id.0.to_string()),
+                                        score: Some(score.score),
+                                    })
+                                    .collect(),
+                            ),
+                        }),
+                    )
+                    .await?;
+                }
+                lobby_match::Events::RoundPlayed(rounds_played) => {
+                    Self::update_lobby_match_rounds_played(
+                        &mut *transaction,
+                        match_id,
+                        *rounds_played,
                     )
                     .await?;
                 }
+                lobby_match::Events::Started(event) => {
+                    Self::insert_lobby_match(&mut *transaction, match_id, event).await?;
+                    Self::delete_lobby_scores(&mut *transaction, event.lobby_id).await?;
+                }
             }
         }
 
-        transaction.commit().await?;
         Ok(())
     }
 
-    pub async fn save_user(
-        pool: &Pool<Postgres>,
+    async fn clear_game_player_votes<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET vote_id = NULL
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1);"#,
+            game_id.0,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn clear_game_player_votes_against<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET vote_id = NULL
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1)
+              AND vote_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            game_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_deck<'c, E>(executor: E, deck_id: deck_id::DeckId) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE FROM deck
+            WHERE public_id = $1;"#,
+            deck_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_deck_topics<'c, E>(
+        executor: E,
+        deck_id: deck_id::DeckId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE FROM deck_topic
+            WHERE deck_id = (SELECT id FROM deck WHERE public_id = $1);"#,
+            deck_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_lobby<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE FROM lobby
+            WHERE public_id = $1;"#,
+            lobby_id.0,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_lobby_ban<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE
+            FROM lobby_ban
+            WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1)
+                AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            lobby_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_lobby_member<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE
+            FROM lobby_member
+            WHERE lobby_id = ((SELECT id FROM lobby WHERE public_id = $1))
+                AND user_id = ((SELECT id FROM "user" WHERE public_id = $2));"#,
+            lobby_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|result| result.rows_affected() > 0)
+    }
+
+    async fn delete_lobby_scores<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"DELETE
+            FROM lobby_score
+            WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1);"#,
+            lobby_id.0,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_deck<'c, E>(
+        executor: E,
+        id: deck_id::DeckId,
+        owner: user_id::UserId,
+        name: &str,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO deck (public_id, owner_id, name)
+            VALUES ($1, (SELECT id FROM "user" WHERE public_id = $2), $3);"#,
+            id.0,
+            owner.0,
+            name,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_deck_topic<'c, E>(
+        executor: E,
+        deck_id: deck_id::DeckId,
+        topic: &topic::Topic,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO deck_topic (deck_id, title, words)
+            VALUES ((SELECT id FROM deck WHERE public_id = $1), $2, $3);"#,
+            deck_id.0,
+            topic.title,
+            &topic.words().cloned().collect::<Vec<_>>(),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_game<'c, E>(
+        executor: E,
+        id: game_id::GameId,
+        event: &game::StartedEvent,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO game (public_id,
+                              lobby_id,
+                              topic,
+                              words,
+                              secret_word,
+                              tie_break,
+                              seed,
+                              roll_d6,
+                              roll_d8,
+                              lobby_match_id,
+                              round,
+                              clue_time_limit,
+                              vote_time_limit)
+            VALUES ($1,
+                    (SELECT id FROM lobby WHERE public_id = $2),
+                    $3,
+                    $4,
+                    $5,
+                    $6,
+                    $7,
+                    $8,
+                    $9,
+                    (SELECT id FROM lobby_match WHERE public_id = $10),
+                    $11,
+                    $12,
+                    $13);"#,
+            id.0,
+            event.lobby_id.0,
+            event.topic.title,
+            &event.topic.words().cloned().collect::<Vec<_>>(),
+            i32::try_from(event.secret_word).unwrap(),
+            event.tie_break.as_str(),
+            // stored bit for bit, postgres has no unsigned integers
+            i64::from_ne_bytes(event.seed.to_ne_bytes()),
+            i16::from(event.roll.d6),
+            i16::from(event.roll.d8),
+            event.match_id.0,
+            i32::try_from(event.round).unwrap(),
+            i32::try_from(event.time_limits.clue).unwrap(),
+            i32::try_from(event.time_limits.vote).unwrap(),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_game_player<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        user_id: user_id::UserId,
+        chameleon: bool,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO game_player (game_id, user_id, chameleon)
+            VALUES ((SELECT id FROM game WHERE public_id = $1),
+                    (SELECT id FROM "user" WHERE public_id = $2),
+                    $3);"#,
+            game_id.0,
+            user_id.0,
+            chameleon
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby<'c, E>(
+        executor: E,
+        id: lobby_id::LobbyId,
+        event: &lobby::CreatedEvent,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby (public_id,
+                               name,
+                               passcode,
+                               require_passcode,
+                               tie_break,
+                               deck_id,
+                               rounds,
+                               target_score,
+                               clue_time_limit,
+                               vote_time_limit,
+                               max_members,
+                               visibility)
+            VALUES ($1, $2, $3, $4, $5, (SELECT id FROM deck WHERE public_id = $6), $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (public_id) DO UPDATE
+                SET name = $2,
+                    passcode = $3,
+                    require_passcode = $4,
+                    tie_break = $5,
+                    deck_id = (SELECT id FROM deck WHERE public_id = $6),
+                    rounds = $7,
+                    target_score = $8,
+                    clue_time_limit = $9,
+                    vote_time_limit = $10,
+                    max_members = $11,
+                    visibility = $12;"#,
+            id.0,
+            event.name,
+            event.passcode.as_ref().map(passcode::Passcode::as_hash),
+            event.require_passcode,
+            event.tie_break.as_str(),
+            event.deck.custom().map(|deck_id| deck_id.0),
+            event
+                .goal
+                .rounds()
+                .map(|rounds| i32::try_from(rounds).unwrap()),
+            event
+                .goal
+                .target_score()
+                .map(|target_score| i32::try_from(target_score).unwrap()),
+            i32::try_from(event.time_limits.clue).unwrap(),
+            i32::try_from(event.time_limits.vote).unwrap(),
+            i32::try_from(event.max_members).unwrap(),
+            event.visibility.as_str(),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_ban<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_ban (lobby_id, user_id)
+            VALUES ((SELECT id FROM lobby WHERE public_id = $1),
+                    (SELECT id FROM "user" WHERE public_id = $2))
+            ON CONFLICT DO NOTHING;"#,
+            lobby_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_chat_message<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        chat_message: &lobby::ChatMessageEvent,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_chat_message (public_id, lobby_id, user_id, message)
+            VALUES ($1,
+                    (SELECT id FROM lobby WHERE public_id = $2),
+                    (SELECT id FROM "user" WHERE public_id = $3),
+                    $4);"#,
+            chat_message.id.0,
+            lobby_id.0,
+            chat_message.user_id.0,
+            chat_message.message
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_invite<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        invite: &lobby::Invite,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_invite (public_id, lobby_id, expires_at, max_uses, revoked)
+            VALUES ($1, (SELECT id FROM lobby WHERE public_id = $2), $3, $4, $5);"#,
+            invite.id.0,
+            lobby_id.0,
+            invite.expires_at,
+            i32::try_from(invite.max_uses).unwrap(),
+            invite.revoked
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// Redeem the invite unless it is used up, failing with `RowNotFound` if it is.
+    async fn insert_lobby_invite_redemption<'c, E>(
+        executor: E,
+        invite_id: invite_id::InviteId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let redeemed = sqlx::query!(
+            r#"INSERT INTO lobby_invite_redemption (lobby_invite_id, user_id)
+            SELECT li.id, (SELECT id FROM "user" WHERE public_id = $2)
+            FROM lobby_invite li
+            WHERE li.public_id = $1
+              AND (SELECT COUNT(*) FROM lobby_invite_redemption lir WHERE lir.lobby_invite_id = li.id)
+                < li.max_uses;"#,
+            invite_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await?
+        .rows_affected();
+
+        if redeemed == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn insert_lobby_match<'c, E>(
+        executor: E,
+        id: match_id::MatchId,
+        event: &lobby_match::StartedEvent,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_match (public_id, lobby_id, rounds, target_score)
+            VALUES ($1, (SELECT id FROM lobby WHERE public_id = $2), $3, $4);"#,
+            id.0,
+            event.lobby_id.0,
+            event
+                .goal
+                .rounds()
+                .map(|rounds| i32::try_from(rounds).unwrap()),
+            event
+                .goal
+                .target_score()
+                .map(|target_score| i32::try_from(target_score).unwrap()),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_match_standing<'c, E>(
+        executor: E,
+        match_id: match_id::MatchId,
+        score: &lobby::Score,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_match_standing (lobby_match_id, user_id, score)
+            VALUES ((SELECT id FROM lobby_match WHERE public_id = $1),
+                    (SELECT id FROM "user" WHERE public_id = $2),
+                    $3);"#,
+            match_id.0,
+            score.user_id.0,
+            i32::try_from(score.score).unwrap()
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_member<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_member (lobby_id, user_id, host)
+            VALUES ((SELECT id FROM lobby WHERE public_id = $1),
+                    (SELECT id FROM "user" WHERE public_id = $2),
+                    $3)
+            ON CONFLICT DO NOTHING;"#,
+            lobby_id.0,
+            user_id.0,
+            false
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_local<'c, E>(
+        conn: E,
+        local_id: local_id::LocalId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO local (public_id, user_id)
+            VALUES ($1,
+                    (SELECT id FROM "user" WHERE "user".public_id = $2));"#,
+            local_id.0,
+            user_id.0
+        )
+        .execute(conn)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_user<'c, E>(conn: E, id: user_id::UserId, name: &str) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO "user" (public_id, name)
+            VALUES ($1, $2);"#,
+            id.0,
+            name,
+        )
+        .execute(conn)
+        .await
+        .map(|_| ())
+    }
+
+    async fn insert_lobby_score<'c, E>(
+        executor: E,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+        points: u32,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO lobby_score (lobby_id, user_id, score)
+            VALUES ((SELECT id FROM lobby WHERE public_id = $1),
+                    (SELECT id FROM "user" WHERE public_id = $2),
+                    $3)
+            ON CONFLICT (lobby_id, user_id) DO UPDATE
+                SET score = lobby_score.score + $3;"#,
+            lobby_id.0,
+            user_id.0,
+            i32::try_from(points).unwrap()
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// Lock the lobby row first, so transactions touching the same lobby queue up instead of
+    /// deadlocking on member rows and the event sequence.
+    async fn lock_lobby<'c, E>(executor: E, lobby_id: lobby_id::LobbyId) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT id
+            FROM lobby
+            WHERE public_id = $1
+            FOR UPDATE;"#,
+            lobby_id.0
+        )
+        .fetch_optional(executor)
+        .await
+        .map(|_| ())
+    }
+
+    /// Log the frame under the next sequence number of the lobby and notify it, the lobby row
+    /// stays locked until commit so frames are delivered in sequence.
+    async fn notify(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        recipient: Option<user_id::UserId>,
+        lobby_request: LobbyRequest,
+    ) -> Result<(), sqlx::Error> {
+        let seq = sqlx::query_scalar!(
+            r#"UPDATE lobby
+            SET event_seq = event_seq + 1
+            WHERE public_id = $1
+            RETURNING event_seq;"#,
+            lobby_id.0
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let frame = LobbyFrame::new_sequenced_request(seq, lobby_request);
+
+        sqlx::query!(
+            r#"INSERT INTO lobby_event (lobby_id, seq, recipient_id, frame)
+            VALUES ((SELECT id FROM lobby WHERE public_id = $1),
+                    $2,
+                    (SELECT id FROM "user" WHERE public_id = $3),
+                    $4);"#,
+            lobby_id.0,
+            seq,
+            recipient.map(|recipient| recipient.0),
+            frame.to_string().unwrap()
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"DELETE
+            FROM lobby_event
+            WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1)
+              AND seq <= $2;"#,
+            lobby_id.0,
+            seq - LOBBY_EVENT_RETENTION
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let notification = serde_json::to_string(&LobbyNotification { recipient, frame }).unwrap();
+
+        sqlx::query!(
+            r#"SELECT pg_notify($1, $2)"#,
+            format!("/lobbies/{}", lobby_id.0),
+            notification
+        )
+        .execute(&mut *transaction)
+        .await
+        .map(|_| ())
+    }
+
+    /// Notify every member of the lobby.
+    async fn notify_lobby(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        lobby_request: LobbyRequest,
+    ) -> Result<(), sqlx::Error> {
+        Self::notify(transaction, lobby_id, None, lobby_request).await
+    }
+
+    /// Notify a single member of the lobby.
+    async fn notify_lobby_user(
+        transaction: &mut Transaction<'_, Postgres>,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+        lobby_request: LobbyRequest,
+    ) -> Result<(), sqlx::Error> {
+        Self::notify(transaction, lobby_id, Some(user_id), lobby_request).await
+    }
+
+    async fn select_deck(
+        conn: &mut PgConnection,
+        deck_id: deck_id::DeckId,
+    ) -> Result<Option<deck::Deck>, sqlx::Error> {
+        let Some(deck) = sqlx::query!(
+            r#"SELECT d.id, d.public_id, d.name, u.public_id owner_public_id
+            FROM deck d
+                     JOIN "user" u ON u.id = d.owner_id
+            WHERE d.public_id = $1;"#,
+            deck_id.0
+        )
+        .fetch_optional(&mut *conn)
+        .await? else {
+            return Ok(None);
+        };
+
+        let topics = sqlx::query!(
+            r#"SELECT dt.title, dt.words
+            FROM deck_topic dt
+            WHERE dt.deck_id = $1
+            ORDER BY dt.id;"#,
+            deck.id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(Some(deck::Deck {
+            id: deck_id::DeckId(deck.public_id),
+            name: deck.name,
+            owner: user_id::UserId(deck.owner_public_id),
+            topics: topics
+                .into_iter()
+                .map(|topic| topic::Topic::new(&topic.title, &topic.words).unwrap())
+                .collect(),
+        }))
+    }
+
+    async fn select_game(
+        conn: &mut PgConnection,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<game::Game>, sqlx::Error> {
+        let Some(game) = sqlx::query!(
+            r#"SELECT g.id,
+                   g.public_id,
+                   g.topic,
+                   g.words,
+                   g.secret_word,
+                   g.roll_d6,
+                   g.roll_d8,
+                   g.guess,
+                   g.phase,
+                   g.tie_break,
+                   g.clue_time_limit,
+                   g.vote_time_limit,
+                   g.deadline,
+                   a.public_id "accused_public_id?"
+            FROM game g
+                     JOIN lobby l ON l.id = g.lobby_id
+                     LEFT JOIN "user" a ON a.id = g.accused_id
+            WHERE l.public_id = $1
+            ORDER BY g.id DESC
+            LIMIT 1;"#,
+            lobby_id.0
+        )
+        .fetch_optional(&mut *conn)
+        .await? else {
+            return Ok(None);
+        };
+
+        let players = sqlx::query!(
+            r#"SELECT u.public_id,
+                   gp.candidate,
+                   gp.chameleon,
+                   gp.clue,
+                   gp.departed,
+                   gp.skipped,
+                   v.public_id "vote_public_id?"
+            FROM game_player gp
+                     JOIN "user" u ON u.id = gp.user_id
+                     LEFT JOIN "user" v ON v.id = gp.vote_id
+            WHERE gp.game_id = $1
+            ORDER BY gp.id;"#,
+            game.id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(Some(game::Game {
+            id: game_id::GameId(game.public_id),
+            accused: game.accused_public_id.map(user_id::UserId),
+            deadline: game.deadline,
+            topic: topic::Topic::new(&game.topic, &game.words).unwrap(),
+            guess: game.guess,
+            phase: game.phase.parse().unwrap(),
+            players: players
+                .into_iter()
+                .map(|player| game::Player {
+                    candidate: player.candidate,
+                    chameleon: player.chameleon,
+                    clue: player.clue,
+                    departed: player.departed,
+                    skipped: player.skipped,
+                    user_id: user_id::UserId(player.public_id),
+                    vote: player.vote_public_id.map(user_id::UserId),
+                })
+                .collect(),
+            roll: game
+                .roll_d6
+                .zip(game.roll_d8)
+                .map(|(d6, d8)| code_card::Roll {
+                    d6: u8::try_from(d6).unwrap(),
+                    d8: u8::try_from(d8).unwrap(),
+                }),
+            secret_word: usize::try_from(game.secret_word).unwrap(),
+            tie_break: game.tie_break.parse().unwrap(),
+            time_limits: time_limits(game.clue_time_limit, game.vote_time_limit),
+        }))
+    }
+    async fn select_lobby(
+        conn: &mut PgConnection,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<lobby::Lobby>, sqlx::Error> {
+        let Some(lobby) = sqlx::query!(
+            r#"SELECT l.public_id,
+                   d.public_id "deck_public_id?",
+                   l.drawn_topics,
+                   l.rounds,
+                   l.target_score,
+                   l.max_members,
+                   l.name,
+                   l.passcode,
+                   l.require_passcode,
+                   l.tie_break,
+                   l.clue_time_limit,
+                   l.vote_time_limit,
+                   l.visibility,
+                   u.public_id host_public_id
+            FROM lobby l
+                     JOIN lobby_member lm ON l.id = lm.lobby_id
+                     JOIN "user" u ON u.id = lm.user_id
+                     LEFT JOIN deck d ON d.id = l.deck_id
+            WHERE l.public_id = $1
+              AND lm.host IS TRUE;"#,
+            lobby_id.0
+        )
+        .fetch_optional(&mut *conn)
+        .await? else {
+            return Ok(None);
+        };
+
+        let members = sqlx::query!(
+            r#"SELECT u.public_id, lm.host, lm.online, lm.ready
+            FROM lobby_member lm
+                     JOIN "user" u on lm.user_id = u.id
+                     JOIN lobby l on lm.lobby_id = l.id
+            WHERE l.public_id = $1
+            ORDER BY lm.id;"#,
+            lobby_id.0
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let bans = sqlx::query!(
+            r#"SELECT u.public_id
+            FROM lobby_ban lb
+                     JOIN "user" u on lb.user_id = u.id
+                     JOIN lobby l on lb.lobby_id = l.id
+            WHERE l.public_id = $1
+            ORDER BY lb.id;"#,
+            lobby_id.0
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let invites = Self::select_lobby_invites(&mut *conn, lobby_id).await?;
+
+        Ok(Some(lobby::Lobby {
+            id: lobby_id::LobbyId(lobby.public_id),
+            bans: bans
+                .into_iter()
+                .map(|ban| user_id::UserId(ban.public_id))
+                .collect(),
+            deck: lobby.deck_public_id.map_or(lobby::Deck::Default, |deck| {
+                lobby::Deck::Custom(deck_id::DeckId(deck))
+            }),
+            drawn_topics: lobby.drawn_topics,
+            goal: goal(lobby.rounds, lobby.target_score),
+            invites,
+            max_members: u32::try_from(lobby.max_members).unwrap(),
+            name: lobby.name,
+            members: members
+                .into_iter()
+                .map(|member| lobby::Member {
+                    host: member.host,
+                    online: member.online,
+                    ready: member.ready,
+                    user_id: user_id::UserId(member.public_id),
+                })
+                .collect(),
+            passcode: lobby.passcode.map(passcode::Passcode::from_hash),
+            require_passcode: lobby.require_passcode,
+            tie_break: lobby.tie_break.parse().unwrap(),
+            time_limits: time_limits(lobby.clue_time_limit, lobby.vote_time_limit),
+            visibility: lobby.visibility.parse().unwrap(),
+        }))
+    }
+    async fn select_lobby_invites<'c, E>(
+        conn: E,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Vec<lobby::Invite>, sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"SELECT li.public_id,
+                   li.expires_at,
+                   li.max_uses,
+                   li.revoked,
+                   (SELECT COUNT(*) FROM lobby_invite_redemption lir WHERE lir.lobby_invite_id = li.id) "uses!"
+            FROM lobby_invite li
+                     JOIN lobby l on li.lobby_id = l.id
+            WHERE l.public_id = $1
+            ORDER BY li.id;"#,
+            lobby_id.0
+        )
+        .fetch_all(conn)
+        .await
+        .map(|records| {
+            records
+                .into_iter()
+                .map(|record| lobby::Invite {
+                    id: invite_id::InviteId(record.public_id),
+                    expires_at: record.expires_at,
+                    max_uses: u32::try_from(record.max_uses).unwrap(),
+                    revoked: record.revoked,
+                    uses: u32::try_from(record.uses).unwrap(),
+                })
+                .collect()
+        })
+    }
+
+    async fn select_match(
+        conn: &mut PgConnection,
+        lobby_id: lobby_id::LobbyId,
+    ) -> Result<Option<lobby_match::Match>, sqlx::Error> {
+        let Some(record) = sqlx::query!(
+            r#"SELECT lm.public_id, lm.rounds, lm.target_score, lm.rounds_played, lm.finished
+            FROM lobby l
+                     JOIN lobby_match lm on l.id = lm.lobby_id
+            WHERE l.public_id = $1
+            ORDER BY lm.id DESC
+            LIMIT 1;"#,
+            lobby_id.0
+        )
+        .fetch_optional(&mut *conn)
+        .await? else {
+            return Ok(None);
+        };
+
+        let scores = if record.finished {
+            sqlx::query!(
+                r#"SELECT u.public_id, lms.score
+                FROM lobby_match lm
+                         JOIN lobby_match_standing lms on lm.id = lms.lobby_match_id
+                         JOIN "user" u on u.id = lms.user_id
+                WHERE lm.public_id = $1
+                ORDER BY lms.score DESC, lms.id;"#,
+                record.public_id
+            )
+            .map(|record| lobby::Score {
+                user_id: user_id::UserId(record.public_id),
+                score: u32::try_from(record.score).unwrap(),
+            })
+            .fetch_all(&mut *conn)
+            .await?
+        } else {
+            Self::query_lobby_score(&mut *conn, lobby_id).await?
+        };
+
+        Ok(Some(lobby_match::Match {
+            id: match_id::MatchId(record.public_id),
+            finished: record.finished,
+            goal: goal(record.rounds, record.target_score),
+            rounds_played: u32::try_from(record.rounds_played).unwrap(),
+            scores,
+        }))
+    }
+
+    async fn update_deck<'c, E>(
+        executor: E,
+        deck_id: deck_id::DeckId,
+        name: &str,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE deck
+            SET name = $2
+            WHERE public_id = $1;"#,
+            deck_id.0,
+            name,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_accused<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        accused: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game
+            SET accused_id = (SELECT id FROM "user" WHERE public_id = $2)
+            WHERE public_id = $1;"#,
+            game_id.0,
+            accused.0,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_guess<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        guess: &str,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game
+            SET guess = $2
+            WHERE public_id = $1;"#,
+            game_id.0,
+            guess,
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_phase<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        phase: game::Phase,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game
+            SET phase = $2
+            WHERE public_id = $1;"#,
+            game_id.0,
+            phase.as_str(),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_player_candidates<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        candidates: &[user_id::UserId],
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET candidate = user_id IN (SELECT id FROM "user" WHERE public_id = ANY ($2))
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1);"#,
+            game_id.0,
+            &candidates
+                .iter()
+                .map(|user_id| user_id.0)
+                .collect::<Vec<_>>(),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_deadline<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        deadline: Option<OffsetDateTime>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game
+            SET deadline = $2
+            WHERE public_id = $1;"#,
+            game_id.0,
+            deadline
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_player_clue<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        user_id: user_id::UserId,
+        clue: &str,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET clue = $3
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            game_id.0,
+            user_id.0,
+            clue
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_player_departed<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET departed = TRUE, candidate = FALSE, vote_id = NULL
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            game_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_player_skipped<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET skipped = TRUE
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            game_id.0,
+            user_id.0
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
+    }
+
+    async fn update_game_player_vote<'c, E>(
+        executor: E,
+        game_id: game_id::GameId,
         user_id: user_id::UserId,
-        events: &[user::Events],
-    ) -> Result<(), sqlx::Error> {
-        let mut transaction = pool.begin().await?;
-
-        for event in events {
-            match event {
-                user::Events::Created(event) => {
-                    Self::insert_user(&mut transaction, user_id, &event.name).await?;
-                }
-                user::Events::Linked(local_id) => {
-                    Self::insert_local(&mut transaction, *local_id, user_id).await?;
-                }
-                user::Events::Updated(event) => {
-                    Self::update_user(&mut transaction, user_id, &event.name).await?;
-                }
-            }
-        }
-
-        transaction.commit().await?;
-        Ok(())
+        vote: Option<user_id::UserId>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"UPDATE game_player
+            SET vote_id = (SELECT id FROM "user" WHERE public_id = $3)
+            WHERE game_id = (SELECT id FROM game WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            game_id.0,
+            user_id.0,
+            vote.map(|vote| vote.0),
+        )
+        .execute(executor)
+        .await
+        .map(|_| ())
     }
 
-    async fn delete_lobby<'c, E>(
+    async fn update_lobby<'c, E>(
         executor: E,
         lobby_id: lobby_id::LobbyId,
+        event: &lobby::UpdatedEvent,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"DELETE FROM lobby
-            WHERE public_id = $1;"#,
+            r#"UPDATE lobby
+            SET name = $2,
+                passcode = $3,
+                require_passcode = $4,
+                tie_break = $5,
+                deck_id = (SELECT id FROM deck WHERE public_id = $6),
+                rounds = $7,
+                target_score = $8,
+                clue_time_limit = $9,
+                vote_time_limit = $10,
+                max_members = $11,
+                visibility = $12
+            WHERE public_id = $1"#,
             lobby_id.0,
+            event.name,
+            event.passcode.as_ref().map(passcode::Passcode::as_hash),
+            event.require_passcode,
+            event.tie_break.as_str(),
+            event.deck.custom().map(|deck_id| deck_id.0),
+            event
+                .goal
+                .rounds()
+                .map(|rounds| i32::try_from(rounds).unwrap()),
+            event
+                .goal
+                .target_score()
+                .map(|target_score| i32::try_from(target_score).unwrap()),
+            i32::try_from(event.time_limits.clue).unwrap(),
+            i32::try_from(event.time_limits.vote).unwrap(),
+            i32::try_from(event.max_members).unwrap(),
+            event.visibility.as_str(),
         )
         .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn delete_lobby_member<'c, E>(
+    async fn update_lobby_drawn_topics_append<'c, E>(
         executor: E,
         lobby_id: lobby_id::LobbyId,
-        user_id: user_id::UserId,
-    ) -> Result<bool, sqlx::Error>
+        title: &str,
+    ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"DELETE
-            FROM lobby_member
-            WHERE lobby_id = ((SELECT id FROM lobby WHERE public_id = $1))
-                AND user_id = ((SELECT id FROM "user" WHERE public_id = $2));"#,
+            r#"UPDATE lobby
+            SET drawn_topics = array_append(drawn_topics, $2)
+            WHERE public_id = $1;"#,
             lobby_id.0,
-            user_id.0
+            title,
         )
         .execute(executor)
         .await
-        .map(|result| result.rows_affected() > 0)
+        .map(|_| ())
     }
 
-    async fn insert_lobby<'c, E>(
+    async fn update_lobby_drawn_topics_clear<'c, E>(
         executor: E,
-        id: lobby_id::LobbyId,
-        name: &str,
-        passcode: &Option<String>,
-        require_passcode: bool,
+        lobby_id: lobby_id::LobbyId,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"INSERT INTO lobby (public_id, name, passcode, require_passcode)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT (public_id) DO UPDATE
-                SET name = $2,
-                    passcode = $3,
-                    require_passcode = $4;"#,
-            id.0,
-            name,
-            passcode.clone(),
-            require_passcode,
+            r#"UPDATE lobby
+            SET drawn_topics = '{}'
+            WHERE public_id = $1;"#,
+            lobby_id.0,
         )
         .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn insert_lobby_member<'c, E>(
+    async fn update_lobby_invite_revoked<'c, E>(
         executor: E,
-        lobby_id: lobby_id::LobbyId,
-        user_id: user_id::UserId,
+        invite_id: invite_id::InviteId,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"INSERT INTO lobby_member (lobby_id, user_id, host)
-            VALUES ((SELECT id FROM lobby WHERE public_id = $1),
-                    (SELECT id FROM "user" WHERE public_id = $2),
-                    $3)
-            ON CONFLICT DO NOTHING;"#,
-            lobby_id.0,
-            user_id.0,
-            false
+            r#"UPDATE lobby_invite
+            SET revoked = TRUE
+            WHERE public_id = $1;"#,
+            invite_id.0
         )
         .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn insert_local<'c, E>(
-        conn: E,
-        local_id: local_id::LocalId,
-        user_id: user_id::UserId,
+    async fn update_lobby_match_finished<'c, E>(
+        executor: E,
+        match_id: match_id::MatchId,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"INSERT INTO local (public_id, user_id)
-            VALUES ($1,
-                    (SELECT id FROM "user" WHERE "user".public_id = $2));"#,
-            local_id.0,
-            user_id.0
+            r#"UPDATE lobby_match
+            SET finished = TRUE
+            WHERE public_id = $1;"#,
+            match_id.0,
         )
-        .execute(conn)
+        .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn insert_user<'c, E>(conn: E, id: user_id::UserId, name: &str) -> Result<(), sqlx::Error>
+    async fn update_lobby_match_rounds_played<'c, E>(
+        executor: E,
+        match_id: match_id::MatchId,
+        rounds_played: u32,
+    ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"INSERT INTO "user" (public_id, name)
-            VALUES ($1, $2);"#,
-            id.0,
-            name,
+            r#"UPDATE lobby_match
+            SET rounds_played = $2
+            WHERE public_id = $1;"#,
+            match_id.0,
+            i32::try_from(rounds_played).unwrap(),
         )
-        .execute(conn)
+        .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn notify_lobby<'c, E>(
-        conn: E,
+    async fn update_lobby_member_host<'c, E>(
+        executor: E,
         lobby_id: lobby_id::LobbyId,
-        lobby_request: LobbyRequest,
+        user_id: user_id::UserId,
+        host: bool,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
-        let frame = LobbyFrame::new_request(None, lobby_request)
-            .to_string()
-            .unwrap();
-
         sqlx::query!(
-            r#"SELECT pg_notify($1, $2)"#,
-            format!("/lobbies/{}", lobby_id.0),
-            frame
+            r#"UPDATE lobby_member
+            SET host = $3
+            WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
+            lobby_id.0,
+            user_id.0,
+            host
         )
-        .execute(conn)
+        .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn update_lobby<'c, E>(
+    async fn update_lobby_member_online<'c, E>(
         executor: E,
         lobby_id: lobby_id::LobbyId,
-        name: &str,
-        passcode: &Option<String>,
-        require_passcode: bool,
+        user_id: user_id::UserId,
+        online: bool,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
-            r#"UPDATE lobby
-            SET name = $2,
-                passcode = $3,
-                require_passcode = $4
-            WHERE public_id = $1"#,
+            r#"UPDATE lobby_member
+            SET online = $3
+            WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1)
+              AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
             lobby_id.0,
-            name,
-            passcode.clone(),
-            require_passcode,
+            user_id.0,
+            online
         )
         .execute(executor)
         .await
         .map(|_| ())
     }
 
-    async fn update_lobby_member_host<'c, E>(
+    async fn update_lobby_member_ready<'c, E>(
         executor: E,
         lobby_id: lobby_id::LobbyId,
         user_id: user_id::UserId,
-        host: bool,
+        ready: bool,
     ) -> Result<(), sqlx::Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         sqlx::query!(
             r#"UPDATE lobby_member
-            SET host = $3
+            SET ready = $3
             WHERE lobby_id = (SELECT id FROM lobby WHERE public_id = $1)
               AND user_id = (SELECT id FROM "user" WHERE public_id = $2);"#,
             lobby_id.0,
             user_id.0,
-            host
+            ready
         )
         .execute(executor)
         .await
@@ -497,39 +2547,360 @@ impl Database {
     }
 }
 
+/// Goal from its columns, exactly one of which is set.
+fn goal(rounds: Option<i32>, target_score: Option<i32>) -> lobby::Goal {
+    match (rounds, target_score) {
+        (Some(rounds), _) => lobby::Goal::Rounds(u32::try_from(rounds).unwrap()),
+        (None, Some(target_score)) => {
+            lobby::Goal::TargetScore(u32::try_from(target_score).unwrap())
+        }
+        (None, None) => unreachable!("guarded by a check constraint"),
+    }
+}
+
+/// Time limits from their columns.
+fn time_limits(clue: i32, vote: i32) -> lobby::TimeLimits {
+    lobby::TimeLimits {
+        clue: u32::try_from(clue).unwrap(),
+        vote: u32::try_from(vote).unwrap(),
+    }
+}
+
+/// Payload of a notification on a lobby channel.
+///
+/// Postgres limits channel names to 63 bytes, so frames for a single member share the lobby
+/// channel and are filtered by the recipient instead.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct LobbyNotification {
+    /// Member the frame is addressed to, every member if `None`.
+    pub recipient: Option<user_id::UserId>,
+
+    pub frame: LobbyFrame,
+}
+
+impl LobbyNotification {
+    /// Is addressed to the user
+    pub fn is_addressed_to(&self, user_id: user_id::UserId) -> bool {
+        self.recipient.is_none_or(|recipient| recipient == user_id)
+    }
+
+    /// Sequence number of the frame
+    pub fn seq(&self) -> Option<i64> {
+        let FrameType::Request(request) = &self.frame.type_ else {
+            return None;
+        };
+
+        request.seq
+    }
+
+    /// Is the kick of the user
+    pub fn is_kick_of(&self, user_id: user_id::UserId) -> bool {
+        let FrameType::Request(request) = &self.frame.type_ else {
+            return false;
+        };
+
+        matches!(
+            &request.data,
+            LobbyRequest::UserKicked(kicked) if kicked.user_id == Some(user_id.0.to_string())
+        )
+    }
+}
+
+/// Pagination by a cursor, pages backwards from `before` if present.
 #[derive(Debug, Clone, Copy)]
-pub struct KeysetPagination {
-    pub id: i64,
+pub struct KeysetPagination<C = i64> {
+    pub after: Option<C>,
+
+    pub before: Option<C>,
 
     pub limit: i64,
 }
 
-impl TryFrom<jsonapi::Pagination> for KeysetPagination {
-    type Error = jsonapi::Error;
+impl<C> KeysetPagination<C> {
+    fn is_backward(&self) -> bool {
+        self.before.is_some()
+    }
+
+    /// One more than the limit, the extra record tells if there are more.
+    fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+
+    /// Page the records fetched with `fetch_limit`, in the order of the query.
+    fn page<R>(&self, mut records: Vec<R>, cursor: impl Fn(&R) -> C) -> Page<R, C> {
+        let limit = usize::try_from(self.limit).unwrap();
+        let more = records.len() > limit;
+        records.truncate(limit);
+
+        let (has_prev, has_next) = if self.is_backward() {
+            records.reverse();
+            (more, true)
+        } else {
+            (self.after.is_some(), more)
+        };
+
+        Page {
+            prev: records.first().filter(|_| has_prev).map(&cursor),
+            next: records.last().filter(|_| has_next).map(&cursor),
+            items: records,
+        }
+    }
+}
+
+impl KeysetPagination {
+    fn lower(&self) -> i64 {
+        self.after.unwrap_or(0)
+    }
+
+    fn upper(&self) -> i64 {
+        self.before.unwrap_or(i64::MAX)
+    }
+}
+
+impl<C> TryFrom<jsonapi::Pagination> for KeysetPagination<C>
+where
+    C: std::str::FromStr,
+{
+    type Error = Box<jsonapi::Error>;
 
     fn try_from(value: jsonapi::Pagination) -> Result<Self, Self::Error> {
-        let parser = |default, value: Option<String>, name: &str| {
+        let parser = |value: Option<String>, name: &str| {
             value
-                .map_or(Ok(default), |s| s.parse::<i64>())
-                .map_err(|error| jsonapi::Error {
-                    status: 400,
-                    source: Source {
-                        header: None,
-                        parameter: name.to_string().into(),
-                        pointer: None,
-                    }
-                    .into(),
-                    title: "Invalid Query Parameter".to_string().into(),
-                    detail: error.to_string().into(),
-                })
+                .map(|value| value.parse::<C>())
+                .transpose()
+                .map_err(|_| Box::new(invalid_query_parameter(name, "Invalid cursor".to_string())))
         };
 
-        let after = parser(0, value.after, "page[after]")?;
-        let size = parser(10, value.size, "page[size]")?;
+        let after = parser(value.after, "page[after]")?;
+        let before = parser(value.before, "page[before]")?;
+
+        let limit = value
+            .size
+            .map_or(Ok(10), |size| size.parse::<i64>())
+            .map_err(|error| Box::new(invalid_query_parameter("page[size]", error.to_string())))?;
+
+        if limit < 1 {
+            return Err(Box::new(invalid_query_parameter(
+                "page[size]",
+                "Page size must be positive".to_string(),
+            )));
+        }
 
         Ok(KeysetPagination {
-            id: after,
-            limit: size,
+            after,
+            before,
+            limit,
+        })
+    }
+}
+
+/// A page of records, with the cursors of the neighbouring pages if there are any.
+#[derive(Debug, Clone)]
+pub struct Page<T, C = i64> {
+    pub items: Vec<T>,
+
+    pub prev: Option<C>,
+
+    pub next: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U, C> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            prev: self.prev,
+            next: self.next,
+        }
+    }
+}
+
+/// Filter of the lobby query, `None` does not filter.
+#[derive(Debug, Clone, Default)]
+pub struct LobbyFilter {
+    /// Case-insensitive part of the name.
+    pub name: Option<String>,
+
+    pub require_passcode: Option<bool>,
+
+    /// Has fewer members than it can hold.
+    pub has_space: Option<bool>,
+}
+
+impl TryFrom<attributes::LobbyFilter> for LobbyFilter {
+    type Error = Box<jsonapi::Error>;
+
+    fn try_from(value: attributes::LobbyFilter) -> Result<Self, Self::Error> {
+        let parser = |value: Option<String>, name: &str| {
+            value
+                .map(|s| s.parse::<bool>())
+                .transpose()
+                .map_err(|error| Box::new(invalid_query_parameter(name, error.to_string())))
+        };
+
+        Ok(LobbyFilter {
+            name: value.name.filter(|name| !name.is_empty()),
+            require_passcode: parser(value.require_passcode, "filter[require_passcode]")?,
+            has_space: parser(value.has_space, "filter[has_space]")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbySortKey {
+    CreatedAt,
+    Id,
+    MemberCount,
+    Name,
+}
+
+impl LobbySortKey {
+    fn column(self) -> &'static str {
+        match self {
+            LobbySortKey::CreatedAt => "l.created_at",
+            LobbySortKey::Id => "l.id",
+            LobbySortKey::MemberCount => "l.member_count",
+            LobbySortKey::Name => "l.name",
+        }
+    }
+}
+
+/// Sort of the lobby query, each key descending if `true`.
+#[derive(Debug, Clone, Default)]
+pub struct LobbySort(pub Vec<(LobbySortKey, bool)>);
+
+impl TryFrom<jsonapi::Sort> for LobbySort {
+    type Error = Box<jsonapi::Error>;
+
+    fn try_from(value: jsonapi::Sort) -> Result<Self, Self::Error> {
+        let Some(sort) = value.sort else {
+            return Ok(LobbySort::default());
+        };
+
+        sort.split(',')
+            .map(|field| {
+                let (field, descending) = match field.strip_prefix('-') {
+                    Some(field) => (field, true),
+                    None => (field, false),
+                };
+
+                let key = match field {
+                    "created_at" => LobbySortKey::CreatedAt,
+                    "member_count" => LobbySortKey::MemberCount,
+                    "name" => LobbySortKey::Name,
+                    _ => {
+                        return Err(Box::new(invalid_query_parameter(
+                            "sort",
+                            "Sort fields must be one of created_at, member_count, name".to_string(),
+                        )))
+                    }
+                };
+
+                Ok((key, descending))
+            })
+            .collect::<Result<_, _>>()
+            .map(LobbySort)
+    }
+}
+
+/// Lobby bounding a page, with every sort key so any sort can continue from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbyCursor {
+    pub id: i64,
+
+    pub created_at: OffsetDateTime,
+
+    pub member_count: i64,
+
+    pub name: String,
+}
+
+impl LobbyCursor {
+    /// Push the condition of records after the cursor in the order of the keys, or before it.
+    fn push_keyset(
+        &self,
+        keys: &[(LobbySortKey, bool)],
+        before: bool,
+        builder: &mut QueryBuilder<Postgres>,
+    ) {
+        builder.push(" AND (FALSE");
+        for (index, (key, descending)) in keys.iter().enumerate() {
+            builder.push(" OR (TRUE");
+            for (previous, _) in &keys[..index] {
+                builder.push(format!(" AND {} = ", previous.column()));
+                self.push_bind(*previous, builder);
+            }
+            builder.push(format!(
+                " AND {} {} ",
+                key.column(),
+                if *descending == before { ">" } else { "<" }
+            ));
+            self.push_bind(*key, builder);
+            builder.push(")");
+        }
+        builder.push(")");
+    }
+
+    fn push_bind(&self, key: LobbySortKey, builder: &mut QueryBuilder<Postgres>) {
+        match key {
+            LobbySortKey::CreatedAt => builder.push_bind(self.created_at),
+            LobbySortKey::Id => builder.push_bind(self.id),
+            LobbySortKey::MemberCount => builder.push_bind(self.member_count),
+            LobbySortKey::Name => builder.push_bind(self.name.clone()),
+        };
+    }
+}
+
+/// `id.member_count.created_at.name`, with `created_at` in unix microseconds.
+impl std::fmt::Display for LobbyCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.id,
+            self.member_count,
+            self.created_at.unix_timestamp_nanos() / 1000,
+            self.name
+        )
+    }
+}
+
+impl std::str::FromStr for LobbyCursor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(4, '.');
+        let mut next = || parts.next().ok_or(());
+
+        let id = next()?.parse().map_err(|_| ())?;
+        let member_count = next()?.parse().map_err(|_| ())?;
+        let created_at = next()?
+            .parse::<i128>()
+            .ok()
+            .and_then(|micros| OffsetDateTime::from_unix_timestamp_nanos(micros * 1000).ok())
+            .ok_or(())?;
+        let name = next()?.to_string();
+
+        Ok(LobbyCursor {
+            id,
+            created_at,
+            member_count,
+            name,
         })
     }
 }
+
+pub type LobbyPagination = KeysetPagination<LobbyCursor>;
+
+fn invalid_query_parameter(name: &str, detail: String) -> jsonapi::Error {
+    jsonapi::Error {
+        status: 400,
+        source: Source {
+            header: None,
+            parameter: name.to_string().into(),
+            pointer: None,
+        }
+        .into(),
+        title: "Invalid Query Parameter".to_string().into(),
+        detail: detail.into(),
+    }
+}
@@ -0,0 +1,61 @@
+use chameleon_protocol::topic::WORD_COUNT;
+
+/// Code card: the d6 picks the row, the d8 the column, and the cell the secret word.
+///
+/// Cells are row-major indices into the topic card, every word appears exactly three times.
+const CODE_CARD: [[usize; 8]; 6] = [
+    [7, 1, 14, 15, 10, 14, 11, 0],
+    [15, 4, 8, 10, 12, 8, 0, 6],
+    [3, 12, 13, 8, 5, 15, 13, 6],
+    [2, 1, 3, 1, 14, 7, 12, 10],
+    [11, 5, 2, 13, 0, 11, 5, 7],
+    [6, 2, 4, 3, 9, 9, 9, 4],
+];
+
+/// Roll of a d6 and a d8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roll {
+    pub d6: u8,
+    pub d8: u8,
+}
+
+impl Roll {
+    /// Roll the dice.
+    pub fn random(rng: &mut impl rand::Rng) -> Self {
+        Self {
+            d6: rng.gen_range(1..=6),
+            d8: rng.gen_range(1..=8),
+        }
+    }
+
+    /// Get the coordinate of the secret word, as a row-major index into the topic card.
+    pub fn get_coordinate(self) -> usize {
+        let coordinate = CODE_CARD[usize::from(self.d6 - 1)][usize::from(self.d8 - 1)];
+        debug_assert!(coordinate < WORD_COUNT);
+        coordinate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_word_appears_three_times() {
+        let mut counts = [0; WORD_COUNT];
+        for coordinate in CODE_CARD.iter().flatten() {
+            counts[*coordinate] += 1;
+        }
+
+        assert_eq!(counts, [3; WORD_COUNT]);
+    }
+
+    #[test]
+    fn every_roll_has_a_coordinate() {
+        for d6 in 1..=6 {
+            for d8 in 1..=8 {
+                assert!(Roll { d6, d8 }.get_coordinate() < WORD_COUNT);
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use chameleon_protocol::topic::{Topic, WORD_COUNT};
+
+use super::{deck_id::DeckId, user_id::UserId};
+
+/// Deck of topics uploaded by a user.
+pub struct Deck {
+    pub id: DeckId,
+    pub name: String,
+    pub owner: UserId,
+    pub topics: Vec<Topic>,
+}
+
+pub struct Query {
+    pub id: DeckId,
+    pub name: String,
+    pub owner: UserId,
+}
+
+impl Deck {
+    pub fn create(
+        name: &str,
+        actor: UserId,
+        topics: &[(String, Vec<String>)],
+    ) -> Result<(Self, Vec<Events>), CreateError> {
+        if name.trim().is_empty() {
+            return Err(CreateError::EmptyName);
+        }
+
+        let topics = parse_topics(topics).map_err(|error| match error {
+            ParseTopicsError::Empty => CreateError::MissingTopics,
+            ParseTopicsError::Invalid(index, error) => CreateError::InvalidTopic(index, error),
+        })?;
+
+        let this = Self {
+            id: DeckId::random(),
+            name: name.trim().to_string(),
+            owner: actor,
+            topics,
+        };
+
+        let events = vec![Events::Created(CreatedEvent {
+            name: this.name.clone(),
+            owner: actor,
+            topics: this.topics.clone(),
+        })];
+
+        Ok((this, events))
+    }
+
+    /// Delete.
+    pub fn delete(&self, actor: UserId) -> Result<Vec<Events>, DeleteError> {
+        if actor != self.owner {
+            return Err(DeleteError::NotOwner);
+        }
+
+        Ok(vec![Events::Deleted])
+    }
+
+    /// Update.
+    ///
+    /// Topics are replaced as a whole.
+    pub fn update(
+        &mut self,
+        actor: UserId,
+        name: Option<&str>,
+        topics: Option<&[(String, Vec<String>)]>,
+    ) -> Result<Vec<Events>, UpdateError> {
+        if actor != self.owner {
+            return Err(UpdateError::NotOwner);
+        }
+
+        if name.is_some_and(|name| name.trim().is_empty()) {
+            return Err(UpdateError::EmptyName);
+        }
+
+        let topics = topics
+            .map(parse_topics)
+            .transpose()
+            .map_err(|error| match error {
+                ParseTopicsError::Empty => UpdateError::MissingTopics,
+                ParseTopicsError::Invalid(index, error) => UpdateError::InvalidTopic(index, error),
+            })?;
+
+        if let Some(name) = name {
+            self.name = name.trim().to_string();
+        }
+
+        if let Some(topics) = topics {
+            self.topics = topics;
+        }
+
+        Ok(vec![Events::Updated(UpdatedEvent {
+            name: self.name.clone(),
+            topics: self.topics.clone(),
+        })])
+    }
+}
+
+/// Parse topics, each must have a unique title and exactly 16 unique non-empty words.
+fn parse_topics(topics: &[(String, Vec<String>)]) -> Result<Vec<Topic>, ParseTopicsError> {
+    if topics.is_empty() {
+        return Err(ParseTopicsError::Empty);
+    }
+
+    let mut titles: Vec<&str> = Vec::with_capacity(topics.len());
+
+    topics
+        .iter()
+        .enumerate()
+        .map(|(index, (title, words))| {
+            let title = title.trim();
+            if title.is_empty() {
+                return Err(ParseTopicsError::Invalid(index, TopicError::EmptyTitle));
+            }
+            if titles.iter().any(|other| other.eq_ignore_ascii_case(title)) {
+                return Err(ParseTopicsError::Invalid(index, TopicError::DuplicateTitle));
+            }
+            titles.push(title);
+
+            if words.len() != WORD_COUNT {
+                return Err(ParseTopicsError::Invalid(index, TopicError::WordCount));
+            }
+
+            let words = words
+                .iter()
+                .map(|word| word.trim().to_string())
+                .collect::<Vec<_>>();
+            if words.iter().any(String::is_empty) {
+                return Err(ParseTopicsError::Invalid(index, TopicError::EmptyWord));
+            }
+            // guesses are matched ignoring case, so words must be too
+            if words.iter().enumerate().any(|(i, word)| {
+                words[..i]
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(word))
+            }) {
+                return Err(ParseTopicsError::Invalid(index, TopicError::DuplicateWord));
+            }
+
+            Ok(Topic::new(title, &words).unwrap())
+        })
+        .collect()
+}
+
+enum ParseTopicsError {
+    Empty,
+    Invalid(usize, TopicError),
+}
+
+pub enum Events {
+    Created(CreatedEvent),
+    Deleted,
+    Updated(UpdatedEvent),
+}
+
+pub struct CreatedEvent {
+    pub name: String,
+    pub owner: UserId,
+    pub topics: Vec<Topic>,
+}
+
+pub struct UpdatedEvent {
+    pub name: String,
+    pub topics: Vec<Topic>,
+}
+
+pub enum CreateError {
+    EmptyName,
+    InvalidTopic(usize, TopicError),
+    MissingTopics,
+}
+
+pub enum DeleteError {
+    NotOwner,
+}
+
+pub enum TopicError {
+    DuplicateTitle,
+    DuplicateWord,
+    EmptyTitle,
+    EmptyWord,
+    WordCount,
+}
+
+pub enum UpdateError {
+    EmptyName,
+    InvalidTopic(usize, TopicError),
+    MissingTopics,
+    NotOwner,
+}
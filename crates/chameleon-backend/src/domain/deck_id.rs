@@ -0,0 +1,9 @@
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct DeckId(pub uuid::Uuid);
+
+impl DeckId {
+    pub fn random() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+}
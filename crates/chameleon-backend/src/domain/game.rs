@@ -0,0 +1,993 @@
+use chameleon_protocol::topic::Topic;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use time::OffsetDateTime;
+
+use super::{
+    code_card::Roll,
+    game_id::GameId,
+    lobby::{Lobby, TieBreak, TimeLimits},
+    lobby_id::LobbyId,
+    lobby_match::Match,
+    match_id::MatchId,
+    user_id::UserId,
+};
+
+/// Minimum number of players required to start a game.
+pub const MIN_PLAYERS: usize = 3;
+
+/// Points for the chameleon when another player is accused.
+pub const ESCAPED_POINTS: u32 = 2;
+
+/// Points for the chameleon when caught but guessing the secret word.
+pub const GUESSED_POINTS: u32 = 1;
+
+/// Points for every other player when the chameleon is caught and guesses wrong.
+pub const CAUGHT_POINTS: u32 = 2;
+
+pub struct Game {
+    pub id: GameId,
+    pub accused: Option<UserId>,
+    /// When the current clue turn or vote runs out of time, `None` without a time limit.
+    pub deadline: Option<OffsetDateTime>,
+    pub topic: Topic,
+    pub guess: Option<String>,
+    pub phase: Phase,
+    /// Players in turn order.
+    pub players: Vec<Player>,
+    /// Dice rolled for the secret word, `None` for games started before dice were rolled.
+    pub roll: Option<Roll>,
+    pub secret_word: usize,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Clues,
+    Voting,
+    TieBreak,
+    Guessing,
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Another player was accused.
+    Escaped,
+    /// The chameleon was caught but guessed the secret word.
+    Guessed,
+    /// The chameleon was caught and guessed wrong.
+    Caught,
+}
+
+#[allow(clippy::struct_excessive_bools)] // reason = "one column per flag"
+pub struct Player {
+    /// Can be voted for; narrowed down to the tied players after a tied vote.
+    pub candidate: bool,
+    pub chameleon: bool,
+    pub clue: Option<String>,
+    /// Left the lobby during the game, and no longer takes part.
+    pub departed: bool,
+    /// Ran out of time before giving a clue.
+    pub skipped: bool,
+    pub user_id: UserId,
+    pub vote: Option<UserId>,
+}
+
+impl Game {
+    /// Start the next round of the match.
+    ///
+    /// The chameleon and the roll of the dice are derived from the seed alone, through
+    /// [`ChaCha8Rng`] as its stream is fixed across releases of rand, so a game can be replayed
+    /// from its seed, players and topic. The first player to give a clue rotates each round. Only
+    /// one game runs at a time, the current one must have finished.
+    pub fn start(
+        lobby: &Lobby,
+        lobby_match: &Match,
+        current: Option<&Game>,
+        actor: UserId,
+        topic: Topic,
+        seed: u64,
+    ) -> Result<(Self, Vec<Events>), StartError> {
+        if lobby.get_host() != actor {
+            return Err(StartError::NotHost);
+        }
+
+        if current.is_some_and(|game| game.phase != Phase::Finished) {
+            return Err(StartError::GameInProgress);
+        }
+
+        if lobby.members.len() < MIN_PLAYERS {
+            return Err(StartError::NotEnoughPlayers);
+        }
+
+        if !lobby.members.iter().all(|member| member.ready) {
+            return Err(StartError::MembersNotReady);
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let chameleon = rng.gen_range(0..lobby.members.len());
+        let roll = Roll::random(&mut rng);
+        let secret_word = roll.get_coordinate();
+
+        let mut players = lobby
+            .members
+            .iter()
+            .enumerate()
+            .map(|(seat, member)| Player {
+                candidate: true,
+                chameleon: seat == chameleon,
+                clue: None,
+                departed: false,
+                skipped: false,
+                user_id: member.user_id,
+                vote: None,
+            })
+            .collect::<Vec<_>>();
+        let round = lobby_match.rounds_played + 1;
+        let first_player = usize::try_from(lobby_match.rounds_played).unwrap() % players.len();
+        players.rotate_left(first_player);
+
+        let mut this = Self {
+            id: GameId::random(),
+            accused: None,
+            deadline: None,
+            topic,
+            guess: None,
+            phase: Phase::Clues,
+            players,
+            roll: Some(roll),
+            secret_word,
+            tie_break: lobby.tie_break,
+            time_limits: lobby.time_limits,
+        };
+
+        let mut events = vec![Events::Started(Box::new(StartedEvent {
+            lobby_id: lobby.id,
+            match_id: lobby_match.id,
+            round,
+            topic: this.topic.clone(),
+            roll,
+            secret_word,
+            seed,
+            tie_break: this.tie_break,
+            time_limits: this.time_limits,
+        }))];
+
+        events.extend(this.players.iter().map(|player| {
+            Events::Dealt(DealtEvent {
+                user_id: player.user_id,
+                chameleon: player.chameleon,
+                secret_word: this
+                    .get_secret_word(player.user_id)
+                    .map(ToString::to_string),
+            })
+        }));
+
+        events.extend(this.restart_deadline());
+
+        Ok((this, events))
+    }
+
+    /// Decide a tied vote.
+    ///
+    /// Only the host may decide, and only between the tied players.
+    pub fn decide_tie(
+        &mut self,
+        lobby: &Lobby,
+        actor: UserId,
+        accused: UserId,
+    ) -> Result<Vec<Events>, DecideTieError> {
+        if lobby.get_host() != actor {
+            return Err(DecideTieError::NotHost);
+        }
+
+        if self.phase != Phase::TieBreak {
+            return Err(DecideTieError::WrongPhase);
+        }
+
+        if !self.is_candidate(accused) {
+            return Err(DecideTieError::InvalidCandidate);
+        }
+
+        let mut events = self.accuse(accused);
+        events.extend(self.restart_deadline());
+
+        Ok(events)
+    }
+
+    /// Expire the current clue turn, vote or tie break once its deadline has passed.
+    ///
+    /// The player whose turn it is skips their clue, players yet to vote abstain, and the
+    /// chameleon escapes a tie the host did not decide.
+    pub fn expire(&mut self) -> Result<Vec<Events>, ExpireError> {
+        let Some(deadline) = self.deadline else {
+            return Err(ExpireError::NoDeadline);
+        };
+
+        if OffsetDateTime::now_utc() < deadline {
+            return Err(ExpireError::NotExpired);
+        }
+
+        match self.phase {
+            Phase::Clues => {
+                let player = self
+                    .players
+                    .iter_mut()
+                    .find(|player| player.is_waiting_for_clue())
+                    .unwrap();
+                player.skipped = true;
+
+                let mut events = vec![Events::ClueSkipped(player.user_id)];
+                events.extend(self.end_turn());
+
+                Ok(events)
+            }
+            Phase::Voting => Ok(self.reveal_votes()),
+            Phase::TieBreak => {
+                let mut events = self.finish();
+                events.extend(self.restart_deadline());
+
+                Ok(events)
+            }
+            Phase::Guessing | Phase::Finished => Err(ExpireError::NoDeadline),
+        }
+    }
+
+    /// Get the player whose turn it is to give a clue.
+    ///
+    /// Players give clues in turn order; `None` outside of the clue phase.
+    pub fn get_turn(&self) -> Option<UserId> {
+        if self.phase != Phase::Clues {
+            return None;
+        }
+
+        self.players
+            .iter()
+            .find(|player| player.is_waiting_for_clue())
+            .map(|player| player.user_id)
+    }
+
+    /// Get outcome, once finished.
+    pub fn get_outcome(&self) -> Option<Outcome> {
+        if self.phase != Phase::Finished {
+            return None;
+        }
+
+        let chameleon = self.players.iter().find(|player| player.chameleon)?;
+        if self.accused != Some(chameleon.user_id) {
+            Some(Outcome::Escaped)
+        } else if self.guess.as_deref() == self.topic.word(self.secret_word) {
+            Some(Outcome::Guessed)
+        } else {
+            Some(Outcome::Caught)
+        }
+    }
+
+    /// Get roll of the dice as seen by the user.
+    ///
+    /// `None` for users not playing, and for the chameleon until the game is finished.
+    pub fn get_roll(&self, user_id: UserId) -> Option<Roll> {
+        self.roll.filter(|_| self.knows_secret_word(user_id))
+    }
+
+    /// Get secret word as seen by the user.
+    ///
+    /// `None` for users not playing, and for the chameleon until the game is finished.
+    pub fn get_secret_word(&self, user_id: UserId) -> Option<&str> {
+        self.knows_secret_word(user_id)
+            .then(|| self.topic.word(self.secret_word))
+            .flatten()
+    }
+
+    /// Guess the secret word.
+    ///
+    /// Only the caught chameleon may guess, once, from the words on the card.
+    pub fn guess(&mut self, actor: UserId, word: &str) -> Result<Vec<Events>, GuessError> {
+        if !self.is_chameleon(actor) {
+            return Err(GuessError::NotChameleon);
+        }
+
+        if self.phase != Phase::Guessing {
+            return Err(GuessError::WrongPhase);
+        }
+
+        let Some(word) = self
+            .topic
+            .words()
+            .find(|candidate| candidate.eq_ignore_ascii_case(word.trim())) else {
+                return Err(GuessError::InvalidWord)
+            };
+
+        self.guess = Some(word.clone());
+
+        let mut events = vec![Events::Guessed(word.clone())];
+        events.extend(self.finish());
+
+        Ok(events)
+    }
+
+    /// Is candidate
+    pub fn is_candidate(&self, user_id: UserId) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.user_id == user_id && player.candidate)
+    }
+
+    /// Is chameleon
+    pub fn is_chameleon(&self, user_id: UserId) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.user_id == user_id && player.chameleon)
+    }
+
+    /// Is player
+    pub fn is_player(&self, user_id: UserId) -> bool {
+        self.players.iter().any(|player| player.user_id == user_id)
+    }
+
+    /// Remove a player who left the lobby.
+    ///
+    /// Their clue turn is skipped, their vote and the votes against them are dropped and they
+    /// can no longer be voted for. The game ends once the chameleon leaves.
+    pub fn remove_player(&mut self, user_id: UserId) -> Result<Vec<Events>, RemovePlayerError> {
+        if self.phase == Phase::Finished {
+            return Err(RemovePlayerError::WrongPhase);
+        }
+
+        let turn = self.get_turn();
+
+        let Some(player) = self
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == user_id && !player.departed) else {
+                return Err(RemovePlayerError::NotPlayer)
+            };
+        player.departed = true;
+        player.candidate = false;
+        player.vote = None;
+        let chameleon = player.chameleon;
+
+        for player in &mut self.players {
+            if player.vote == Some(user_id) {
+                player.vote = None;
+            }
+        }
+
+        let mut events = vec![Events::Departed(user_id)];
+
+        if chameleon {
+            events.extend(self.finish());
+            events.extend(self.restart_deadline());
+            return Ok(events);
+        }
+
+        match self.phase {
+            Phase::Clues if turn == Some(user_id) => events.extend(self.end_turn()),
+            Phase::Voting | Phase::TieBreak => {
+                let candidates = self
+                    .players
+                    .iter()
+                    .filter(|player| player.candidate)
+                    .map(|player| player.user_id)
+                    .collect::<Vec<_>>();
+
+                if let [accused] = candidates[..] {
+                    events.extend(self.accuse(accused));
+                    events.extend(self.restart_deadline());
+                } else if self.phase == Phase::Voting && self.is_every_vote_cast() {
+                    events.extend(self.reveal_votes());
+                }
+            }
+            Phase::Clues | Phase::Guessing | Phase::Finished => {}
+        }
+
+        Ok(events)
+    }
+
+    /// Submit clue.
+    pub fn submit_clue(
+        &mut self,
+        actor: UserId,
+        clue: &str,
+    ) -> Result<Vec<Events>, SubmitClueError> {
+        let clue = clue.trim();
+        if clue.is_empty() || clue.contains(char::is_whitespace) {
+            return Err(SubmitClueError::InvalidClue);
+        }
+
+        let Some(player) = self
+            .players
+            .iter()
+            .find(|player| player.user_id == actor) else {
+                return Err(SubmitClueError::NotPlayer)
+            };
+
+        if self.phase != Phase::Clues {
+            return Err(SubmitClueError::WrongPhase);
+        }
+
+        if player.clue.is_some() || player.skipped {
+            return Err(SubmitClueError::AlreadySubmitted);
+        }
+
+        if self.get_turn() != Some(actor) {
+            return Err(SubmitClueError::NotTurn);
+        }
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == actor)
+            .unwrap();
+        player.clue = Some(clue.to_string());
+
+        let mut events = vec![Events::ClueSubmitted(ClueSubmittedEvent {
+            user_id: actor,
+            clue: clue.to_string(),
+        })];
+        events.extend(self.end_turn());
+
+        Ok(events)
+    }
+
+    /// Vote for the player suspected of being the chameleon.
+    ///
+    /// Votes are revealed once every player has voted.
+    pub fn vote(&mut self, actor: UserId, accused: UserId) -> Result<Vec<Events>, VoteError> {
+        let Some(player) = self
+            .players
+            .iter()
+            .find(|player| player.user_id == actor && !player.departed) else {
+                return Err(VoteError::NotPlayer)
+            };
+
+        if self.phase != Phase::Voting {
+            return Err(VoteError::WrongPhase);
+        }
+
+        if player.vote.is_some() {
+            return Err(VoteError::AlreadyVoted);
+        }
+
+        if actor == accused || !self.is_candidate(accused) {
+            return Err(VoteError::InvalidCandidate);
+        }
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == actor)
+            .unwrap();
+        player.vote = Some(accused);
+
+        let mut events = vec![Events::VoteCast(VoteCastEvent {
+            user_id: actor,
+            accused,
+        })];
+
+        if self.is_every_vote_cast() {
+            events.extend(self.reveal_votes());
+        }
+
+        Ok(events)
+    }
+
+    fn accuse(&mut self, accused: UserId) -> Vec<Events> {
+        self.accused = Some(accused);
+
+        let mut events = vec![Events::Accused(accused)];
+
+        if self.is_chameleon(accused) {
+            self.phase = Phase::Guessing;
+            events.push(Events::PhaseChanged(self.phase));
+        } else {
+            events.extend(self.finish());
+        }
+
+        events
+    }
+
+    /// End the clue turn, voting starts once every player has had their turn.
+    fn end_turn(&mut self) -> Vec<Events> {
+        let mut events = Vec::new();
+
+        if !self.players.iter().any(Player::is_waiting_for_clue) {
+            self.phase = Phase::Voting;
+            events.push(Events::PhaseChanged(self.phase));
+        }
+
+        events.extend(self.restart_deadline());
+
+        events
+    }
+
+    fn finish(&mut self) -> Vec<Events> {
+        self.phase = Phase::Finished;
+        let outcome = self.get_outcome().unwrap();
+
+        let points = self
+            .players
+            .iter()
+            .filter(|player| !player.departed)
+            .filter_map(|player| {
+                let points = match (outcome, player.chameleon) {
+                    (Outcome::Escaped, true) => ESCAPED_POINTS,
+                    (Outcome::Guessed, true) => GUESSED_POINTS,
+                    (Outcome::Caught, false) => CAUGHT_POINTS,
+                    _ => return None,
+                };
+                Some((player.user_id, points))
+            })
+            .collect();
+
+        vec![
+            Events::Scored(ScoredEvent { outcome, points }),
+            Events::PhaseChanged(self.phase),
+        ]
+    }
+
+    fn is_every_vote_cast(&self) -> bool {
+        self.players
+            .iter()
+            .all(|player| player.departed || player.vote.is_some())
+    }
+
+    fn knows_secret_word(&self, user_id: UserId) -> bool {
+        self.players.iter().any(|player| {
+            player.user_id == user_id && (!player.chameleon || self.phase == Phase::Finished)
+        })
+    }
+
+    /// Restart the deadline for the current clue turn, vote or tie break, if it has a time limit.
+    ///
+    /// The host has as long to decide a tie as the players had to vote.
+    ///
+    /// Deadlines are truncated to whole seconds to survive a round trip through the database.
+    fn restart_deadline(&mut self) -> Option<Events> {
+        let time_limit = match self.phase {
+            Phase::Clues => self.time_limits.get_clue(),
+            Phase::Voting | Phase::TieBreak => self.time_limits.get_vote(),
+            Phase::Guessing | Phase::Finished => None,
+        };
+
+        let deadline = time_limit.map(|time_limit| {
+            let deadline = OffsetDateTime::now_utc() + time_limit;
+            OffsetDateTime::from_unix_timestamp(deadline.unix_timestamp()).unwrap()
+        });
+
+        if deadline.is_none() && self.deadline.is_none() {
+            return None;
+        }
+
+        self.deadline = deadline;
+
+        Some(Events::DeadlineChanged(DeadlineChangedEvent {
+            phase: self.phase,
+            turn: self.get_turn(),
+            deadline,
+        }))
+    }
+
+    fn reveal_votes(&mut self) -> Vec<Events> {
+        let candidates = self
+            .players
+            .iter()
+            .filter(|player| player.candidate)
+            .count();
+        let tally = self
+            .players
+            .iter()
+            .filter(|player| player.candidate)
+            .map(|candidate| {
+                let count = self
+                    .players
+                    .iter()
+                    .filter(|player| player.vote == Some(candidate.user_id))
+                    .count();
+                (candidate.user_id, count)
+            })
+            .collect::<Vec<_>>();
+
+        let most = tally
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or_default();
+        let tied = tally
+            .into_iter()
+            .filter(|(_, count)| *count == most)
+            .map(|(user_id, _)| user_id)
+            .collect::<Vec<_>>();
+
+        let mut events = vec![Events::VotesRevealed(VotesRevealedEvent {
+            votes: self
+                .players
+                .iter()
+                .filter_map(|player| player.vote.map(|vote| (player.user_id, vote)))
+                .collect(),
+            accused: (tied.len() == 1).then(|| tied[0]),
+            tied: tied.clone(),
+        })];
+
+        if let [accused] = tied[..] {
+            events.extend(self.accuse(accused));
+            events.extend(self.restart_deadline());
+            return events;
+        }
+
+        // nobody voted or the tie did not narrow down, another vote would tie again so the
+        // chameleon escapes
+        let stalemate = tied.len() == candidates;
+
+        for player in &mut self.players {
+            player.candidate = tied.contains(&player.user_id);
+        }
+        events.push(Events::Tied(tied));
+
+        match self.tie_break {
+            TieBreak::Host => {
+                self.phase = Phase::TieBreak;
+                events.push(Events::PhaseChanged(self.phase));
+            }
+            TieBreak::Revote if stalemate => events.extend(self.finish()),
+            TieBreak::Revote => {
+                for player in &mut self.players {
+                    player.vote = None;
+                }
+                events.push(Events::VotesCleared);
+            }
+        }
+
+        events.extend(self.restart_deadline());
+
+        events
+    }
+}
+
+impl Player {
+    /// Is yet to give a clue, their turn having neither passed nor been skipped.
+    fn is_waiting_for_clue(&self) -> bool {
+        self.clue.is_none() && !self.skipped && !self.departed
+    }
+}
+
+impl Phase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Phase::Clues => "clues",
+            Phase::Voting => "voting",
+            Phase::TieBreak => "tie_break",
+            Phase::Guessing => "guessing",
+            Phase::Finished => "finished",
+        }
+    }
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Escaped => "escaped",
+            Outcome::Guessed => "guessed",
+            Outcome::Caught => "caught",
+        }
+    }
+}
+
+impl std::str::FromStr for Phase {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clues" => Ok(Phase::Clues),
+            "voting" => Ok(Phase::Voting),
+            "tie_break" => Ok(Phase::TieBreak),
+            "guessing" => Ok(Phase::Guessing),
+            "finished" => Ok(Phase::Finished),
+            _ => Err(()),
+        }
+    }
+}
+
+pub enum Events {
+    Accused(UserId),
+    ClueSkipped(UserId),
+    ClueSubmitted(ClueSubmittedEvent),
+    Dealt(DealtEvent),
+    DeadlineChanged(DeadlineChangedEvent),
+    Departed(UserId),
+    Guessed(String),
+    PhaseChanged(Phase),
+    Scored(ScoredEvent),
+    Started(Box<StartedEvent>),
+    Tied(Vec<UserId>),
+    VoteCast(VoteCastEvent),
+    VotesCleared,
+    VotesRevealed(VotesRevealedEvent),
+}
+
+pub struct ClueSubmittedEvent {
+    pub user_id: UserId,
+    pub clue: String,
+}
+
+pub struct DealtEvent {
+    pub user_id: UserId,
+    pub chameleon: bool,
+    /// Secret word as seen by the player.
+    pub secret_word: Option<String>,
+}
+
+pub struct DeadlineChangedEvent {
+    pub phase: Phase,
+    pub turn: Option<UserId>,
+    pub deadline: Option<OffsetDateTime>,
+}
+
+pub struct ScoredEvent {
+    pub outcome: Outcome,
+    /// player, points scored this round
+    pub points: Vec<(UserId, u32)>,
+}
+
+pub struct StartedEvent {
+    pub lobby_id: LobbyId,
+    pub match_id: MatchId,
+    /// Round of the match, starting at 1.
+    pub round: u32,
+    pub topic: Topic,
+    pub roll: Roll,
+    pub secret_word: usize,
+    /// Seed of the random number generator the game was started with.
+    pub seed: u64,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+}
+
+pub struct VoteCastEvent {
+    pub user_id: UserId,
+    pub accused: UserId,
+}
+
+pub struct VotesRevealedEvent {
+    /// voter, accused
+    pub votes: Vec<(UserId, UserId)>,
+    pub accused: Option<UserId>,
+    pub tied: Vec<UserId>,
+}
+
+pub enum DecideTieError {
+    InvalidCandidate,
+    NotHost,
+    WrongPhase,
+}
+
+pub enum ExpireError {
+    NoDeadline,
+    NotExpired,
+}
+
+pub enum GuessError {
+    InvalidWord,
+    NotChameleon,
+    WrongPhase,
+}
+
+pub enum RemovePlayerError {
+    NotPlayer,
+    WrongPhase,
+}
+
+pub enum StartError {
+    GameInProgress,
+    MembersNotReady,
+    NotEnoughPlayers,
+    NotHost,
+}
+
+pub enum SubmitClueError {
+    AlreadySubmitted,
+    InvalidClue,
+    NotPlayer,
+    NotTurn,
+    WrongPhase,
+}
+
+pub enum VoteError {
+    AlreadyVoted,
+    InvalidCandidate,
+    NotPlayer,
+    WrongPhase,
+}
+
+#[cfg(test)]
+mod tests {
+    use chameleon_protocol::topic::default_deck;
+
+    use super::*;
+    use crate::domain::lobby::{Member, Settings, MAX_MEMBERS};
+
+    fn lobby(members: usize) -> Lobby {
+        let settings = Settings {
+            max_members: MAX_MEMBERS,
+            ..Settings::default()
+        };
+        let Ok((mut lobby, _)) = Lobby::create("Lobby", UserId::random(), settings) else {
+            panic!("lobby not created");
+        };
+
+        // the host joins unready, the others are added ready
+        lobby.members[0].ready = true;
+        lobby.members.extend((1..members).map(|_| Member {
+            host: false,
+            online: true,
+            ready: true,
+            user_id: UserId::random(),
+        }));
+
+        lobby
+    }
+
+    fn start(lobby: &Lobby, current: Option<&Game>, seed: u64) -> Result<Game, StartError> {
+        let (lobby_match, _) = Match::start(lobby);
+        let topic = default_deck().swap_remove(0);
+        Game::start(lobby, &lobby_match, current, lobby.get_host(), topic, seed)
+            .map(|(game, _)| game)
+    }
+
+    fn get_chameleon(game: &Game) -> Option<UserId> {
+        game.players
+            .iter()
+            .find(|player| player.chameleon)
+            .map(|player| player.user_id)
+    }
+
+    #[test]
+    fn start_is_replayable_from_the_seed() {
+        let lobby = lobby(4);
+
+        for seed in [0, 1, 42, u64::MAX] {
+            let (Ok(game), Ok(replay)) = (start(&lobby, None, seed), start(&lobby, None, seed))
+            else {
+                panic!("game not started");
+            };
+
+            assert_eq!(get_chameleon(&game), get_chameleon(&replay));
+            assert_eq!(game.roll, replay.roll);
+            assert_eq!(game.secret_word, replay.secret_word);
+            assert_eq!(Some(game.secret_word), game.roll.map(Roll::get_coordinate));
+        }
+    }
+
+    #[test]
+    fn start_rejects_a_game_in_progress() {
+        let lobby = lobby(3);
+        let Ok(mut current) = start(&lobby, None, 0) else {
+            panic!("game not started");
+        };
+
+        assert!(matches!(
+            start(&lobby, Some(&current), 1),
+            Err(StartError::GameInProgress)
+        ));
+
+        current.phase = Phase::Finished;
+        assert!(start(&lobby, Some(&current), 1).is_ok());
+    }
+
+    fn start_voting(tie_break: TieBreak, time_limits: TimeLimits, members: usize) -> Game {
+        let mut lobby = lobby(members);
+        lobby.tie_break = tie_break;
+        lobby.time_limits = time_limits;
+
+        let Ok(mut game) = start(&lobby, None, 0) else {
+            panic!("game not started");
+        };
+        game.phase = Phase::Voting;
+        game.deadline = None;
+
+        game
+    }
+
+    fn expire_now(game: &mut Game) {
+        game.deadline = Some(OffsetDateTime::now_utc() - time::Duration::SECOND);
+        assert!(game.expire().is_ok());
+    }
+
+    #[test]
+    fn revote_without_votes_lets_the_chameleon_escape() {
+        let mut game = start_voting(TieBreak::Revote, TimeLimits::default(), 3);
+
+        expire_now(&mut game);
+
+        assert_eq!(game.get_outcome(), Some(Outcome::Escaped));
+    }
+
+    #[test]
+    fn revote_tied_again_lets_the_chameleon_escape() {
+        let mut game = start_voting(TieBreak::Revote, TimeLimits::default(), 4);
+        let [a, b, c, d] = [0, 1, 2, 3].map(|seat| game.players[seat].user_id);
+
+        for _ in 0..2 {
+            assert_eq!(game.get_outcome(), None);
+            for (voter, accused) in [(a, b), (b, a), (c, a), (d, b)] {
+                assert!(game.vote(voter, accused).is_ok());
+            }
+        }
+
+        assert_eq!(game.get_outcome(), Some(Outcome::Escaped));
+    }
+
+    fn get_innocents(game: &Game) -> Vec<UserId> {
+        game.players
+            .iter()
+            .filter(|player| !player.chameleon)
+            .map(|player| player.user_id)
+            .collect()
+    }
+
+    #[test]
+    fn remove_player_passes_their_turn() {
+        let Ok(mut game) = start(&lobby(4), None, 0) else {
+            panic!("game not started");
+        };
+        let [first, second] = [0, 1].map(|seat| game.players[seat].user_id);
+
+        assert!(game.remove_player(first).is_ok());
+
+        assert_eq!(game.get_turn(), Some(second));
+        assert!(!game.is_candidate(first));
+        assert!(matches!(
+            game.remove_player(first),
+            Err(RemovePlayerError::NotPlayer)
+        ));
+    }
+
+    #[test]
+    fn remove_player_drops_the_votes_against_them() {
+        let mut game = start_voting(TieBreak::Host, TimeLimits::default(), 4);
+        let chameleon = get_chameleon(&game).unwrap();
+        let [a, b, c] = get_innocents(&game)[..] else {
+            panic!("not three innocents");
+        };
+
+        assert!(game.vote(chameleon, a).is_ok());
+        assert!(game.vote(b, chameleon).is_ok());
+        assert!(game.vote(c, chameleon).is_ok());
+        assert!(game.remove_player(a).is_ok());
+        assert_eq!(game.phase, Phase::Voting);
+
+        assert!(game.vote(chameleon, b).is_ok());
+        assert_eq!(game.accused, Some(chameleon));
+    }
+
+    #[test]
+    fn remove_player_ends_the_game_without_the_chameleon() {
+        let mut game = start_voting(TieBreak::Host, TimeLimits::default(), 3);
+        let chameleon = get_chameleon(&game).unwrap();
+
+        let Ok(events) = game.remove_player(chameleon) else {
+            panic!("chameleon not removed");
+        };
+
+        assert_eq!(game.get_outcome(), Some(Outcome::Escaped));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, Events::Scored(scored) if scored.points.is_empty())));
+    }
+
+    #[test]
+    fn undecided_tie_lets_the_chameleon_escape() {
+        let time_limits = TimeLimits { clue: 0, vote: 30 };
+        let mut game = start_voting(TieBreak::Host, time_limits, 3);
+
+        expire_now(&mut game);
+        assert_eq!(game.phase, Phase::TieBreak);
+        assert!(game.deadline.is_some());
+
+        expire_now(&mut game);
+        assert_eq!(game.get_outcome(), Some(Outcome::Escaped));
+        assert_eq!(game.deadline, None);
+    }
+}
@@ -1,51 +1,255 @@
-use super::{lobby_id::LobbyId, user_id::UserId};
+use chameleon_protocol::topic::Topic;
+use rand::seq::SliceRandom;
+use time::OffsetDateTime;
+
+use super::{
+    chat_message_id::ChatMessageId, deck_id::DeckId, game::MIN_PLAYERS, invite_id::InviteId,
+    lobby_id::LobbyId, passcode::Passcode, user_id::UserId,
+};
+
+/// Most members a lobby can be configured to hold.
+pub const MAX_MEMBERS: u32 = 8;
 
 pub struct Lobby {
     pub id: LobbyId,
+    /// Users who may not join.
+    pub bans: Vec<UserId>,
+    pub deck: Deck,
+    /// Titles of topics drawn since the deck was last exhausted.
+    pub drawn_topics: Vec<String>,
+    pub goal: Goal,
+    pub invites: Vec<Invite>,
+    pub max_members: u32,
     pub name: String,
     pub members: Vec<Member>,
-    pub passcode: Option<String>,
+    pub passcode: Option<Passcode>,
     pub require_passcode: bool,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+    pub visibility: Visibility,
 }
 
 pub struct Member {
     pub host: bool,
+    /// Connected to the lobby socket.
+    pub online: bool,
+    /// Ready for the game to start.
+    pub ready: bool,
+    pub user_id: UserId,
+}
+
+/// Invitation to join, bypassing the visibility and the passcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Invite {
+    pub id: InviteId,
+    pub expires_at: OffsetDateTime,
+    pub max_uses: u32,
+    pub revoked: bool,
+    pub uses: u32,
+}
+
+/// Deck topics are drawn from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Deck {
+    /// Bundled default deck.
+    #[default]
+    Default,
+    /// Deck uploaded by a user.
+    Custom(DeckId),
+}
+
+/// When a match ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// After a number of rounds.
+    Rounds(u32),
+    /// Once a player reaches a score.
+    TargetScore(u32),
+}
+
+/// How a tied vote is resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Host casts the deciding vote among the tied players.
+    #[default]
+    Host,
+    /// Players vote again among the tied players.
+    Revote,
+}
+
+/// Who can find and join the lobby.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+    /// Joinable only by invitation.
+    Private,
+    /// Listed when browsing lobbies.
+    #[default]
+    Public,
+    /// Not listed, joinable by anyone with the link.
+    Unlisted,
+}
+
+/// Time limits of the game phases in seconds, 0 for no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeLimits {
+    /// For each player to give a clue.
+    pub clue: u32,
+    /// For every player to vote.
+    pub vote: u32,
+}
+
+/// Running score of a user across the games of the current match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
     pub user_id: UserId,
+    pub score: u32,
+}
+
+/// Changes to the lobby settings, `None` leaves a setting unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Changes<'a> {
+    pub deck: Option<Deck>,
+    pub goal: Option<Goal>,
+    pub max_members: Option<u32>,
+    pub name: Option<&'a str>,
+    pub passcode: Option<&'a Passcode>,
+    pub require_passcode: Option<bool>,
+    pub tie_break: Option<TieBreak>,
+    pub clue_time_limit: Option<u32>,
+    pub vote_time_limit: Option<u32>,
+    pub visibility: Option<Visibility>,
+}
+
+/// Settings of a new lobby.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Settings<'a> {
+    pub deck: Deck,
+    pub goal: Goal,
+    pub max_members: u32,
+    pub passcode: Option<&'a Passcode>,
+    pub require_passcode: bool,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+    pub visibility: Visibility,
 }
 
 pub struct Query {
     pub id: LobbyId,
+    pub max_members: u32,
+    pub member_count: u32,
     pub name: String,
     pub require_passcode: bool,
 }
 
+pub struct ChatMessage {
+    pub id: ChatMessageId,
+    pub created_at: OffsetDateTime,
+    pub message: String,
+    pub user_id: UserId,
+}
+
+pub struct MemberQuery {
+    pub id: UserId,
+    pub name: String,
+    pub online: bool,
+    pub ready: bool,
+}
+
 impl Lobby {
+    /// Ban users, members among them are kicked.
+    pub fn ban(&mut self, actor: UserId, user_ids: &[UserId]) -> Result<Vec<Events>, BanError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(BanError::NotHost);
+        }
+
+        if user_ids.contains(&actor) {
+            return Err(BanError::BanningSelf);
+        }
+
+        let mut events = Vec::new();
+
+        for user_id in user_ids {
+            if self.bans.contains(user_id) {
+                continue;
+            }
+
+            if self.is_member(*user_id) {
+                self.members.retain(|member| member.user_id != *user_id);
+                events.push(Events::Kicked(*user_id));
+            }
+
+            self.bans.push(*user_id);
+            events.push(Events::Banned(*user_id));
+        }
+
+        Ok(events)
+    }
+
     pub fn create(
         name: &str,
         actor: UserId,
-        passcode: Option<&str>,
-        require_passcode: bool,
+        settings: Settings,
     ) -> Result<(Self, Vec<Events>), CreateError> {
+        let Settings {
+            deck,
+            goal,
+            max_members,
+            passcode,
+            require_passcode,
+            tie_break,
+            time_limits,
+            visibility,
+        } = settings;
+
         if require_passcode && passcode.is_none() {
             return Err(CreateError::MissingPasscode);
         }
 
+        if !goal.is_valid() {
+            return Err(CreateError::InvalidGoal);
+        }
+
+        if !is_valid_max_members(max_members) {
+            return Err(CreateError::InvalidMaxMembers);
+        }
+
         let this = Self {
             id: LobbyId::random(),
+            bans: Vec::new(),
+            deck,
+            drawn_topics: Vec::new(),
+            goal,
+            invites: Vec::new(),
+            max_members,
             name: name.to_string(),
             members: vec![Member {
                 host: true,
+                online: false,
+                ready: false,
                 user_id: actor,
             }],
-            passcode: passcode.map(ToString::to_string),
+            passcode: passcode.cloned(),
             require_passcode,
+            tie_break,
+            time_limits,
+            visibility,
         };
 
         let events = vec![
             Events::Created(CreatedEvent {
+                deck,
+                goal,
+                max_members,
                 name: this.name.clone(),
                 passcode: this.passcode.clone(),
                 require_passcode,
+                tie_break,
+                time_limits,
+                visibility,
             }),
             Events::Joined(actor),
             Events::HostGranted(actor),
@@ -54,6 +258,71 @@ impl Lobby {
         Ok((this, events))
     }
 
+    /// Create an invite, redeemable until it expires or is used up.
+    pub fn create_invite(
+        &mut self,
+        actor: UserId,
+        expires_at: OffsetDateTime,
+        max_uses: u32,
+    ) -> Result<(Invite, Vec<Events>), CreateInviteError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(CreateInviteError::NotHost);
+        }
+
+        if expires_at <= OffsetDateTime::now_utc() {
+            return Err(CreateInviteError::InvalidExpiry);
+        }
+
+        if max_uses == 0 {
+            return Err(CreateInviteError::InvalidMaxUses);
+        }
+
+        let invite = Invite {
+            id: InviteId::random(),
+            expires_at,
+            max_uses,
+            revoked: false,
+            uses: 0,
+        };
+
+        self.invites.push(invite);
+
+        Ok((invite, vec![Events::InviteCreated(invite)]))
+    }
+
+    /// Draw topic.
+    ///
+    /// Topics are not repeated until every topic of the deck has been drawn.
+    pub fn draw_topic(
+        &mut self,
+        deck: &[Topic],
+        rng: &mut impl rand::Rng,
+    ) -> Result<(Topic, Vec<Events>), DrawTopicError> {
+        let mut events = Vec::new();
+
+        let mut remaining = deck
+            .iter()
+            .filter(|topic| !self.drawn_topics.contains(&topic.title))
+            .collect::<Vec<_>>();
+
+        if remaining.is_empty() {
+            self.drawn_topics.clear();
+            events.push(Events::TopicsReshuffled);
+            remaining = deck.iter().collect();
+        }
+
+        let topic = (*remaining.choose(rng).ok_or(DrawTopicError::EmptyDeck)?).clone();
+
+        self.drawn_topics.push(topic.title.clone());
+        events.push(Events::TopicDrawn(topic.title.clone()));
+
+        Ok((topic, events))
+    }
+
     /// Get host
     pub fn get_host(&self) -> UserId {
         self.members
@@ -68,13 +337,28 @@ impl Lobby {
         self.members.iter().any(|member| member.user_id == user_id)
     }
 
-    /// Join.
+    /// Join, with an invite in place of the passcode. The passcode is verified
+    /// by the caller, see [`Passcode::verify`].
     pub fn join(
         &mut self,
         actor: UserId,
-        passcode: Option<&str>,
+        passcode_verified: bool,
+        invite: Option<InviteId>,
     ) -> Result<Vec<Events>, JoinError> {
-        if self.require_passcode && self.passcode.as_ref().map(String::as_str) != passcode {
+        let invite = invite
+            .map(|invite| {
+                self.invites
+                    .iter()
+                    .position(|i| i.id == invite && i.is_redeemable())
+                    .ok_or(JoinError::InvalidInvite)
+            })
+            .transpose()?;
+
+        if invite.is_none() && self.visibility == Visibility::Private {
+            return Err(JoinError::Private);
+        }
+
+        if invite.is_none() && self.require_passcode && !passcode_verified {
             return Err(JoinError::IncorrectPasscode);
         }
 
@@ -82,12 +366,57 @@ impl Lobby {
             return Err(JoinError::AlreadyJoined);
         }
 
+        if self.bans.contains(&actor) {
+            return Err(JoinError::Banned);
+        }
+
+        if self.members.len() >= self.max_members as usize {
+            return Err(JoinError::Full);
+        }
+
         self.members.push(Member {
             user_id: actor,
             host: false,
+            online: false,
+            ready: false,
         });
 
-        Ok(vec![Events::Joined(actor)])
+        let mut events = vec![Events::Joined(actor)];
+
+        if let Some(index) = invite {
+            let invite = &mut self.invites[index];
+            invite.uses += 1;
+            events.push(Events::InviteRedeemed(invite.id, actor));
+        }
+
+        Ok(events)
+    }
+
+    /// Kick members.
+    pub fn kick(&mut self, actor: UserId, user_ids: &[UserId]) -> Result<Vec<Events>, KickError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(KickError::NotHost);
+        }
+
+        if user_ids.contains(&actor) {
+            return Err(KickError::KickingSelf);
+        }
+
+        if !user_ids.iter().all(|user_id| self.is_member(*user_id)) {
+            return Err(KickError::NotMember);
+        }
+
+        self.members
+            .retain(|member| !user_ids.contains(&member.user_id));
+
+        Ok(user_ids
+            .iter()
+            .map(|user_id| Events::Kicked(*user_id))
+            .collect())
     }
 
     /// Leave.
@@ -119,6 +448,47 @@ impl Lobby {
         Ok(events)
     }
 
+    /// Clear whether the members are ready, once a game has started they must ready up again.
+    pub fn reset_ready(&mut self) -> Vec<Events> {
+        self.members
+            .iter_mut()
+            .filter(|member| member.ready)
+            .map(|member| {
+                member.ready = false;
+                Events::ReadyChanged(member.user_id, false)
+            })
+            .collect()
+    }
+
+    /// Revoke an invite.
+    pub fn revoke_invite(
+        &mut self,
+        actor: UserId,
+        invite_id: InviteId,
+    ) -> Result<Vec<Events>, RevokeInviteError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(RevokeInviteError::NotHost);
+        }
+
+        let invite = self
+            .invites
+            .iter_mut()
+            .find(|invite| invite.id == invite_id)
+            .ok_or(RevokeInviteError::NotFound)?;
+
+        if invite.revoked {
+            return Ok(Vec::new());
+        }
+
+        invite.revoked = true;
+
+        Ok(vec![Events::InviteRevoked(invite_id)])
+    }
+
     /// Send chat message.
     pub fn send_chat_message(
         &mut self,
@@ -130,19 +500,115 @@ impl Lobby {
         }
 
         Ok(vec![Events::ChatMessage(ChatMessageEvent {
+            id: ChatMessageId::random(),
             user_id: actor,
             message: message.to_string(),
         })])
     }
 
-    /// Update.
-    pub fn update(
+    /// Set whether the member is connected, unchanged presence is no event.
+    pub fn set_presence(
         &mut self,
         actor: UserId,
-        name: Option<&str>,
-        passcode: Option<&str>,
-        require_passcode: Option<bool>,
-    ) -> Result<Vec<Events>, UpdateError> {
+        online: bool,
+    ) -> Result<Vec<Events>, SetPresenceError> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|member| member.user_id == actor)
+            .ok_or(SetPresenceError::NotMember)?;
+
+        if member.online == online {
+            return Ok(Vec::new());
+        }
+
+        member.online = online;
+
+        Ok(vec![Events::PresenceChanged(actor, online)])
+    }
+
+    /// Toggle whether the member is ready for the game to start.
+    pub fn toggle_ready(&mut self, actor: UserId) -> Result<Vec<Events>, ToggleReadyError> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|member| member.user_id == actor)
+            .ok_or(ToggleReadyError::NotMember)?;
+
+        member.ready = !member.ready;
+
+        Ok(vec![Events::ReadyChanged(actor, member.ready)])
+    }
+
+    /// Transfer host to another member.
+    pub fn transfer_host(
+        &mut self,
+        actor: UserId,
+        user_id: UserId,
+    ) -> Result<Vec<Events>, TransferHostError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(TransferHostError::NotHost);
+        }
+
+        if !self.is_member(user_id) {
+            return Err(TransferHostError::NotMember);
+        }
+
+        if user_id == actor {
+            return Ok(Vec::new());
+        }
+
+        for member in &mut self.members {
+            member.host = member.user_id == user_id;
+        }
+
+        Ok(vec![
+            Events::HostRevoked(actor),
+            Events::HostGranted(user_id),
+        ])
+    }
+
+    /// Lift bans.
+    pub fn unban(&mut self, actor: UserId, user_ids: &[UserId]) -> Result<Vec<Events>, UnbanError> {
+        if !self
+            .members
+            .iter()
+            .any(|member| member.host && member.user_id == actor)
+        {
+            return Err(UnbanError::NotHost);
+        }
+
+        if !user_ids.iter().all(|user_id| self.bans.contains(user_id)) {
+            return Err(UnbanError::NotBanned);
+        }
+
+        self.bans.retain(|ban| !user_ids.contains(ban));
+
+        Ok(user_ids
+            .iter()
+            .map(|user_id| Events::Unbanned(*user_id))
+            .collect())
+    }
+
+    /// Update.
+    pub fn update(&mut self, actor: UserId, changes: Changes) -> Result<Vec<Events>, UpdateError> {
+        let Changes {
+            deck,
+            goal,
+            max_members,
+            name,
+            passcode,
+            require_passcode,
+            tie_break,
+            clue_time_limit,
+            vote_time_limit,
+            visibility,
+        } = changes;
+
         if !self
             .members
             .iter()
@@ -162,72 +628,308 @@ impl Lobby {
             }
         }
 
+        if goal.is_some_and(|goal| !goal.is_valid()) {
+            return Err(UpdateError::InvalidGoal);
+        }
+
+        // a limit below the member count would leave the lobby over capacity
+        if max_members.is_some_and(|max_members| {
+            !is_valid_max_members(max_members) || (max_members as usize) < self.members.len()
+        }) {
+            return Err(UpdateError::InvalidMaxMembers);
+        }
+
+        if let Some(goal) = goal {
+            self.goal = goal;
+        }
+
+        if let Some(max_members) = max_members {
+            self.max_members = max_members;
+        }
+
         if let Some(name) = name {
             self.name = name.to_string();
         }
 
         if let Some(passcode) = passcode {
-            self.passcode = Some(passcode.to_string());
+            self.passcode = Some(passcode.clone());
         }
 
         if let Some(require_passcode) = require_passcode {
             self.require_passcode = require_passcode;
         }
 
-        Ok(vec![Events::Updated(UpdatedEvent {
+        if let Some(tie_break) = tie_break {
+            self.tie_break = tie_break;
+        }
+
+        if let Some(clue_time_limit) = clue_time_limit {
+            self.time_limits.clue = clue_time_limit;
+        }
+
+        if let Some(vote_time_limit) = vote_time_limit {
+            self.time_limits.vote = vote_time_limit;
+        }
+
+        if let Some(visibility) = visibility {
+            self.visibility = visibility;
+        }
+
+        let mut events = Vec::new();
+
+        if let Some(deck) = deck {
+            if deck != self.deck {
+                self.deck = deck;
+                self.drawn_topics.clear();
+                events.push(Events::TopicsReshuffled);
+            }
+        }
+
+        events.push(Events::Updated(UpdatedEvent {
+            deck: self.deck,
+            goal: self.goal,
+            max_members: self.max_members,
             name: self.name.clone(),
             passcode: self.passcode.clone(),
             require_passcode: self.require_passcode,
-        })])
+            tie_break: self.tie_break,
+            time_limits: self.time_limits,
+            visibility: self.visibility,
+        }));
+
+        Ok(events)
+    }
+}
+
+/// Is enough members to start a game, without exceeding [`MAX_MEMBERS`].
+fn is_valid_max_members(max_members: u32) -> bool {
+    max_members as usize >= MIN_PLAYERS && max_members <= MAX_MEMBERS
+}
+
+impl Deck {
+    pub fn custom(self) -> Option<DeckId> {
+        match self {
+            Deck::Default => None,
+            Deck::Custom(deck_id) => Some(deck_id),
+        }
+    }
+}
+
+impl Goal {
+    fn is_valid(self) -> bool {
+        match self {
+            Goal::Rounds(rounds) => rounds > 0,
+            Goal::TargetScore(target_score) => target_score > 0,
+        }
+    }
+
+    /// Number of rounds, if the match ends after a number of rounds.
+    pub fn rounds(self) -> Option<u32> {
+        match self {
+            Goal::Rounds(rounds) => Some(rounds),
+            Goal::TargetScore(_) => None,
+        }
+    }
+
+    /// Target score, if the match ends once a player reaches a score.
+    pub fn target_score(self) -> Option<u32> {
+        match self {
+            Goal::Rounds(_) => None,
+            Goal::TargetScore(target_score) => Some(target_score),
+        }
+    }
+}
+
+impl Default for Goal {
+    fn default() -> Self {
+        Goal::Rounds(3)
+    }
+}
+
+impl Invite {
+    /// Is not revoked, expired or used up.
+    pub fn is_redeemable(&self) -> bool {
+        !self.revoked && self.uses < self.max_uses && OffsetDateTime::now_utc() < self.expires_at
+    }
+}
+
+impl TimeLimits {
+    /// Get the time limit for each clue.
+    pub fn get_clue(self) -> Option<time::Duration> {
+        (self.clue > 0).then(|| time::Duration::seconds(i64::from(self.clue)))
+    }
+
+    /// Get the time limit for voting.
+    pub fn get_vote(self) -> Option<time::Duration> {
+        (self.vote > 0).then(|| time::Duration::seconds(i64::from(self.vote)))
+    }
+}
+
+impl TieBreak {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TieBreak::Host => "host",
+            TieBreak::Revote => "revote",
+        }
+    }
+}
+
+impl std::str::FromStr for TieBreak {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "host" => Ok(TieBreak::Host),
+            "revote" => Ok(TieBreak::Revote),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Visibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Public => "public",
+            Visibility::Unlisted => "unlisted",
+        }
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "private" => Ok(Visibility::Private),
+            "public" => Ok(Visibility::Public),
+            "unlisted" => Ok(Visibility::Unlisted),
+            _ => Err(()),
+        }
     }
 }
 
 pub enum Events {
+    Banned(UserId),
     ChatMessage(ChatMessageEvent),
     Created(CreatedEvent),
     Empty,
     HostGranted(UserId),
     HostRevoked(UserId),
+    InviteCreated(Invite),
+    InviteRedeemed(InviteId, UserId),
+    InviteRevoked(InviteId),
     Joined(UserId),
+    Kicked(UserId),
     Left(UserId),
+    PresenceChanged(UserId, bool),
+    ReadyChanged(UserId, bool),
+    TopicDrawn(String),
+    TopicsReshuffled,
+    Unbanned(UserId),
     Updated(UpdatedEvent),
 }
 
 pub struct ChatMessageEvent {
+    pub id: ChatMessageId,
     pub user_id: UserId,
     pub message: String,
 }
 
 pub struct CreatedEvent {
+    pub deck: Deck,
+    pub goal: Goal,
+    pub max_members: u32,
     pub name: String,
-    pub passcode: Option<String>,
+    pub passcode: Option<Passcode>,
     pub require_passcode: bool,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+    pub visibility: Visibility,
 }
 
 pub struct UpdatedEvent {
+    pub deck: Deck,
+    pub goal: Goal,
+    pub max_members: u32,
     pub name: String,
-    pub passcode: Option<String>,
+    pub passcode: Option<Passcode>,
     pub require_passcode: bool,
+    pub tie_break: TieBreak,
+    pub time_limits: TimeLimits,
+    pub visibility: Visibility,
+}
+
+pub enum BanError {
+    BanningSelf,
+    NotHost,
 }
 
 pub enum CreateError {
+    InvalidGoal,
+    InvalidMaxMembers,
     MissingPasscode,
 }
 
+pub enum CreateInviteError {
+    InvalidExpiry,
+    InvalidMaxUses,
+    NotHost,
+}
+
+pub enum DrawTopicError {
+    EmptyDeck,
+}
+
 pub enum JoinError {
     AlreadyJoined,
+    Banned,
+    Full,
     IncorrectPasscode,
+    InvalidInvite,
+    Private,
+}
+
+pub enum KickError {
+    KickingSelf,
+    NotHost,
+    NotMember,
 }
 
 pub enum LeaveError {
     NotMember,
 }
 
+pub enum RevokeInviteError {
+    NotFound,
+    NotHost,
+}
+
 pub enum SendChatMessageError {
     NotMember,
 }
 
+pub enum SetPresenceError {
+    NotMember,
+}
+
+pub enum ToggleReadyError {
+    NotMember,
+}
+
+pub enum TransferHostError {
+    NotHost,
+    NotMember,
+}
+
+pub enum UnbanError {
+    NotBanned,
+    NotHost,
+}
+
 pub enum UpdateError {
+    InvalidGoal,
+    InvalidMaxMembers,
     MissingPasscode,
     NotHost,
 }
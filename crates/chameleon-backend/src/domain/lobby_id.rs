@@ -1,5 +1,5 @@
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 pub struct LobbyId(pub uuid::Uuid);
 
 impl LobbyId {
@@ -0,0 +1,77 @@
+use super::{
+    lobby::{Goal, Lobby, Score},
+    lobby_id::LobbyId,
+    match_id::MatchId,
+};
+
+/// Series of game rounds played in a lobby until the goal is reached.
+pub struct Match {
+    pub id: MatchId,
+    pub finished: bool,
+    pub goal: Goal,
+    pub rounds_played: u32,
+    /// Standings, highest score first.
+    pub scores: Vec<Score>,
+}
+
+impl Match {
+    /// Start.
+    pub fn start(lobby: &Lobby) -> (Self, Vec<Events>) {
+        let this = Self {
+            id: MatchId::random(),
+            finished: false,
+            goal: lobby.goal,
+            rounds_played: 0,
+            scores: Vec::new(),
+        };
+
+        let events = vec![Events::Started(StartedEvent {
+            lobby_id: lobby.id,
+            goal: this.goal,
+        })];
+
+        (this, events)
+    }
+
+    /// Record a scored round, finishing the match once the goal is reached.
+    ///
+    /// Expects the scores to already include the points of the round.
+    pub fn record_round(&mut self) -> Result<Vec<Events>, RecordRoundError> {
+        if self.finished {
+            return Err(RecordRoundError::Finished);
+        }
+
+        self.rounds_played += 1;
+
+        let mut events = vec![Events::RoundPlayed(self.rounds_played)];
+
+        let finished = match self.goal {
+            Goal::Rounds(rounds) => self.rounds_played >= rounds,
+            Goal::TargetScore(target_score) => {
+                self.scores.iter().any(|score| score.score >= target_score)
+            }
+        };
+
+        if finished {
+            self.finished = true;
+            events.push(Events::Finished(self.scores.clone()));
+        }
+
+        Ok(events)
+    }
+}
+
+pub enum Events {
+    Finished(Vec<Score>),
+    RoundPlayed(u32),
+    Started(StartedEvent),
+}
+
+pub struct StartedEvent {
+    pub lobby_id: LobbyId,
+    pub goal: Goal,
+}
+
+pub enum RecordRoundError {
+    Finished,
+}
@@ -0,0 +1,9 @@
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct MatchId(pub uuid::Uuid);
+
+impl MatchId {
+    pub fn random() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+}
@@ -1,5 +1,15 @@
+pub mod chat_message_id;
+pub mod code_card;
+pub mod deck;
+pub mod deck_id;
+pub mod game;
+pub mod game_id;
+pub mod invite_id;
 pub mod lobby;
 pub mod lobby_id;
+pub mod lobby_match;
 pub mod local_id;
+pub mod match_id;
+pub mod passcode;
 pub mod user;
 pub mod user_id;
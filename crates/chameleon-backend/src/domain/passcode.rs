@@ -0,0 +1,40 @@
+/// Work factor of new hashes, the migration of plaintext passcodes uses the same.
+const COST: u32 = 10;
+
+/// Bcrypt hash of a lobby passcode, the plaintext is never stored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Passcode(String);
+
+impl Passcode {
+    /// Hash a plaintext passcode, on the blocking pool as bcrypt is slow by design.
+    pub async fn hash(passcode: String) -> Self {
+        let hash = tokio::task::spawn_blocking(move || bcrypt::hash(passcode, COST))
+            .await
+            .expect("hashing not to panic")
+            .expect("cost to be valid");
+        Self(hash)
+    }
+
+    /// Wrap a stored hash.
+    pub fn from_hash(hash: String) -> Self {
+        Self(hash)
+    }
+
+    pub fn as_hash(&self) -> &str {
+        &self.0
+    }
+
+    /// Verify a plaintext passcode, in constant time and on the blocking pool.
+    pub async fn verify(&self, passcode: String) -> bool {
+        let hash = self.0.clone();
+        tokio::task::spawn_blocking(move || bcrypt::verify(passcode, &hash))
+            .await
+            .is_ok_and(|verified| verified.unwrap_or(false))
+    }
+}
+
+impl std::fmt::Debug for Passcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Passcode(..)")
+    }
+}
@@ -6,6 +6,7 @@ pub struct User {
 }
 
 impl User {
+    #[allow(clippy::unnecessary_wraps)] // reason = "consistent with other aggregates"
     pub fn signup(actor: LocalId, name: &str) -> Result<(User, Vec<Events>), CreateError> {
         let user = User {
             id: UserId::random(),
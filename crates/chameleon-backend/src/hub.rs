@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use sqlx::{postgres::PgListener, Pool, Postgres};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use crate::{
+    database::{Database, LobbyNotification},
+    domain::lobby_id,
+};
+
+/// Notifications a socket may fall behind by before it misses some.
+const CAPACITY: usize = 64;
+
+/// Wait before reconnecting after a listener failure.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Fans the notifications of a single listener out to the sockets of the process.
+///
+/// A lobby channel is listened on while it has subscribers, so the process holds one
+/// connection for notifications however many sockets are open.
+#[derive(Clone)]
+pub struct Hub {
+    channels: Arc<Mutex<HashMap<String, Channel>>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+struct Channel {
+    sender: broadcast::Sender<Arc<LobbyNotification>>,
+    subscribers: usize,
+}
+
+enum Command {
+    Listen(String, oneshot::Sender<()>),
+    Unlisten(String),
+}
+
+/// Notifications of a lobby, unsubscribed from on drop.
+pub struct Subscription {
+    channel: String,
+    hub: Hub,
+    pub receiver: broadcast::Receiver<Arc<LobbyNotification>>,
+}
+
+impl Hub {
+    /// Spawn the listener.
+    pub fn spawn(pool: Pool<Postgres>) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let hub = Self {
+            channels: Arc::default(),
+            commands,
+        };
+
+        tokio::spawn(listen(pool, hub.channels.clone(), receiver));
+
+        hub
+    }
+
+    /// Subscribe to the notifications of the lobby, the first subscriber waits for its channel
+    /// to be listened on.
+    pub async fn subscribe(&self, lobby_id: lobby_id::LobbyId) -> Subscription {
+        let channel = format!("/lobbies/{}", lobby_id.0);
+
+        let (receiver, listening) = {
+            let mut channels = self.channels.lock().unwrap();
+
+            if let Some(subscribed) = channels.get_mut(&channel) {
+                subscribed.subscribers += 1;
+                (subscribed.sender.subscribe(), None)
+            } else {
+                let (sender, receiver) = broadcast::channel(CAPACITY);
+                channels.insert(
+                    channel.clone(),
+                    Channel {
+                        sender,
+                        subscribers: 1,
+                    },
+                );
+
+                let (ack, listening) = oneshot::channel();
+                self.commands
+                    .send(Command::Listen(channel.clone(), ack))
+                    .ok();
+                (receiver, Some(listening))
+            }
+        };
+
+        if let Some(listening) = listening {
+            // a failed listen is retried by the reconnect
+            listening.await.ok();
+        }
+
+        Subscription {
+            channel,
+            hub: self.clone(),
+            receiver,
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut channels = self.hub.channels.lock().unwrap();
+
+        let Some(subscribed) = channels.get_mut(&self.channel) else {
+            return;
+        };
+
+        subscribed.subscribers -= 1;
+
+        if subscribed.subscribers == 0 {
+            channels.remove(&self.channel);
+            self.hub
+                .commands
+                .send(Command::Unlisten(self.channel.clone()))
+                .ok();
+        }
+    }
+}
+
+/// Listen until every hub is dropped, reconnecting after a failure.
+async fn listen(
+    pool: Pool<Postgres>,
+    channels: Arc<Mutex<HashMap<String, Channel>>>,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+) {
+    loop {
+        let result = match connect(&pool, &channels).await {
+            Ok(mut listener) => relay(&mut listener, &channels, &mut commands).await,
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(()) => return,
+            Err(error) => {
+                tracing::error!(error =? error, "listener failed, reconnecting");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+/// Connect a listener on the channels with subscribers.
+async fn connect(
+    pool: &Pool<Postgres>,
+    channels: &Mutex<HashMap<String, Channel>>,
+) -> Result<PgListener, sqlx::Error> {
+    let mut listener = Database::listener(pool).await?;
+
+    let subscribed = channels.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+    listener
+        .listen_all(subscribed.iter().map(String::as_str))
+        .await?;
+
+    Ok(listener)
+}
+
+/// Apply the commands and relay the notifications, until the commands close or the listener
+/// fails.
+async fn relay(
+    listener: &mut PgListener,
+    channels: &Mutex<HashMap<String, Channel>>,
+    commands: &mut mpsc::UnboundedReceiver<Command>,
+) -> Result<(), sqlx::Error> {
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Listen(channel, ack)) => {
+                    listener.listen(&channel).await?;
+                    ack.send(()).ok();
+                }
+                Some(Command::Unlisten(channel)) => listener.unlisten(&channel).await?,
+                None => return Ok(()),
+            },
+            notification = listener.try_recv() => {
+                let Some(notification) = notification? else {
+                    // the connection is reestablished on the next receive
+                    tracing::warn!("listener connection lost");
+                    continue;
+                };
+
+                let Ok(payload) =
+                    serde_json::from_str::<LobbyNotification>(notification.payload()) else {
+                        tracing::warn!(channel = notification.channel(), "malformed notification");
+                        continue;
+                    };
+
+                if let Some(subscribed) = channels.lock().unwrap().get(notification.channel()) {
+                    // every receiver may have been dropped since
+                    subscribed.sender.send(Arc::new(payload)).ok();
+                }
+            }
+        }
+    }
+}
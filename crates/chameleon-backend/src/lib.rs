@@ -6,6 +6,9 @@ mod database;
 mod domain;
 mod error;
 mod extract;
+mod hub;
+mod presence;
 mod routes;
+mod timer;
 
 pub use app::app;
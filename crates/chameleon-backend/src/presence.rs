@@ -0,0 +1,173 @@
+use std::{future::Future, time::Duration};
+
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    database::Database,
+    domain::{lobby, lobby_id, user_id},
+    routes::api_v1_lobbies,
+};
+
+/// Members offline for this long leave the lobby.
+const GRACE_PERIOD: Duration = Duration::from_mins(2);
+
+/// Connection leases lapse unless renewed within this long, so sockets of a process that died
+/// stop counting.
+const LEASE: Duration = Duration::from_mins(1);
+
+/// Tracks the sockets of the members as connection leases in the database, a member is online
+/// while a lease of theirs is live on any process.
+#[derive(Clone)]
+pub struct Presence {
+    pool: Pool<Postgres>,
+}
+
+/// A socket of a member, its lease ends on drop.
+pub struct Connection {
+    id: i64,
+    lobby_id: lobby_id::LobbyId,
+    presence: Presence,
+    user_id: user_id::UserId,
+}
+
+impl Presence {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Connect a socket of the member, marking them online.
+    pub async fn connect(
+        &self,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<Connection, sqlx::Error> {
+        let id =
+            Database::insert_lobby_member_connection(&self.pool, lobby_id, user_id, LEASE).await?;
+
+        let presence = self.clone();
+        spawn(async move { presence.sync(lobby_id, user_id, false).await });
+
+        Ok(Connection {
+            id,
+            lobby_id,
+            presence: self.clone(),
+            user_id,
+        })
+    }
+
+    /// Check the members marked online by a previous process, those without a live lease are
+    /// disconnected.
+    pub async fn restore(&self) -> Result<(), sqlx::Error> {
+        for (lobby_id, user_id) in Database::query_lobby_member_online(&self.pool).await? {
+            let presence = self.clone();
+            spawn(async move { presence.sync(lobby_id, user_id, true).await });
+        }
+
+        Ok(())
+    }
+
+    /// End the lease of the socket, its end starts the grace period.
+    async fn disconnect(
+        &self,
+        connection_id: i64,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error> {
+        Database::update_lobby_member_connection(&self.pool, connection_id, Duration::ZERO).await?;
+
+        self.sync(lobby_id, user_id, true).await
+    }
+
+    /// Save the presence of the member, after a disconnect they leave unless a lease of theirs
+    /// is live or renewed within the grace period.
+    async fn sync(
+        &self,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+        disconnected: bool,
+    ) -> Result<(), sqlx::Error> {
+        self.save(lobby_id, user_id).await?;
+
+        if !disconnected {
+            return Ok(());
+        }
+
+        tokio::time::sleep(GRACE_PERIOD).await;
+
+        // a socket on any process, or one that closed within the grace period, keeps the member
+        if Database::query_lobby_member_connected(&self.pool, lobby_id, user_id, GRACE_PERIOD)
+            .await?
+        {
+            return Ok(());
+        }
+
+        let Some(mut lobby) = Database::load_lobby(&self.pool, lobby_id).await? else {
+            return Ok(());
+        };
+
+        match lobby.leave(user_id) {
+            Ok(events) => {
+                Database::save_lobby(&self.pool, lobby.id, &events).await?;
+                api_v1_lobbies::remove_players(&self.pool, lobby.id, &[user_id]).await?;
+            }
+            Err(error) => match error {
+                lobby::LeaveError::NotMember => {
+                    // silently continue...
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Save whether the member holds a live lease, reading the leases afresh so the last save
+    /// wins.
+    async fn save(
+        &self,
+        lobby_id: lobby_id::LobbyId,
+        user_id: user_id::UserId,
+    ) -> Result<(), sqlx::Error> {
+        let online =
+            Database::query_lobby_member_connected(&self.pool, lobby_id, user_id, Duration::ZERO)
+                .await?;
+
+        let Some(mut lobby) = Database::load_lobby(&self.pool, lobby_id).await? else {
+            return Ok(());
+        };
+
+        match lobby.set_presence(user_id, online) {
+            Ok(events) => Database::save_lobby(&self.pool, lobby.id, &events).await?,
+            Err(error) => match error {
+                lobby::SetPresenceError::NotMember => {
+                    // left or kicked meanwhile
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl Connection {
+    /// Renew the lease, on every heartbeat of the socket.
+    pub async fn renew(&self) -> Result<(), sqlx::Error> {
+        Database::update_lobby_member_connection(&self.presence.pool, self.id, LEASE).await
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let presence = self.presence.clone();
+        let (id, lobby_id, user_id) = (self.id, self.lobby_id, self.user_id);
+
+        spawn(async move { presence.disconnect(id, lobby_id, user_id).await });
+    }
+}
+
+fn spawn(future: impl Future<Output = Result<(), sqlx::Error>> + Send + 'static) {
+    tokio::spawn(async move {
+        if let Err(error) = future.await {
+            tracing::error!(error =? error, "error");
+        }
+    });
+}
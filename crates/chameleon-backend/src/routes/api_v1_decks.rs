@@ -0,0 +1,325 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header::LOCATION, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post},
+    Json, Router,
+};
+use chameleon_protocol::{
+    attributes::{DeckAttributes, DeckTopicAttributes},
+    jsonapi::{
+        self, Links, Pagination, Relationship, Relationships, ResourceIdentifiers, Resources,
+        ResourcesDocument,
+    },
+};
+
+use crate::{
+    app::AppState,
+    database::Database,
+    domain::{deck, deck_id, local_id, user_id},
+    error::ApiError,
+};
+
+use super::{api_v1_users, page_links, ToResource, ToResourceIdentifier, Variation};
+
+pub const PATH: &str = "/api/v1/decks";
+
+const TYPE: &str = "deck";
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_many))
+        .route("/", post(create_one))
+        .route("/:id", get(get_one))
+        .route("/:id", patch(update_one))
+        .route("/:id", delete(delete_one))
+}
+
+#[tracing::instrument(skip(state))]
+async fn create_one(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Json(document): Json<ResourcesDocument<DeckAttributes>>,
+) -> Result<Response, ApiError> {
+    let name = document.try_get_attribute(|a| a.name.as_ref(), "name", "Name")?;
+    let topics =
+        to_topics(document.try_get_attribute(|a| a.topics.as_ref(), "topics", "Topics")?);
+
+    let deck = match deck::Deck::create(name, user_id, &topics) {
+        Ok((deck, events)) => {
+            Database::save_deck(&state.pool, deck.id, &events).await?;
+            deck
+        }
+        Err(error) => match error {
+            deck::CreateError::EmptyName => return Err(empty_name()),
+            deck::CreateError::InvalidTopic(index, error) => {
+                return Err(invalid_topic(index, &error));
+            }
+            deck::CreateError::MissingTopics => return Err(missing_topics()),
+        },
+    };
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(deck.to_resource(Variation::Root))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}", deck.id.0))].into(),
+        )),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [(LOCATION, format!("{PATH}/{}", deck.id.0))],
+        Json(document),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_one(
+    State(state): State<AppState>,
+    local_id: local_id::LocalId,
+    Path(id): Path<deck_id::DeckId>,
+) -> Result<Response, ApiError> {
+    let deck = Database::load_deck(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("deck", "Deck"))))?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(deck.to_resource(Variation::Root))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}", deck.id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_many(
+    State(state): State<AppState>,
+    local_id: local_id::LocalId,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let keyset_pagination = pagination.try_into()?;
+
+    let page = Database::query_deck(&state.pool, keyset_pagination).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            page.items
+                .iter()
+                .map(|deck| deck.to_resource(Variation::Nested))
+                .collect(),
+        )),
+        errors: None,
+        links: Some(page_links(PATH, &keyset_pagination, &page, "")),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn update_one(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<deck_id::DeckId>,
+    Json(document): Json<ResourcesDocument<DeckAttributes>>,
+) -> Result<Response, ApiError> {
+    let resource = document.try_get_individual()?;
+
+    let mut deck = Database::load_deck(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("deck", "Deck"))))?;
+
+    let name = resource
+        .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
+        .map(String::as_str);
+    let topics = resource
+        .try_get_attribute(|a| a.topics.as_ref(), "topics", "Topics")
+        .map(|topics| to_topics(topics));
+
+    match deck.update(user_id, name.ok(), topics.as_deref().ok()) {
+        Ok(events) => {
+            Database::save_deck(&state.pool, deck.id, &events).await?;
+        }
+        Err(error) => match error {
+            deck::UpdateError::EmptyName => return Err(empty_name()),
+            deck::UpdateError::InvalidTopic(index, error) => {
+                return Err(invalid_topic(index, &error));
+            }
+            deck::UpdateError::MissingTopics => return Err(missing_topics()),
+            deck::UpdateError::NotOwner => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    }
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(deck.to_resource(Variation::Root))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}", deck.id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn delete_one(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<deck_id::DeckId>,
+) -> Result<Response, ApiError> {
+    let deck = Database::load_deck(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("deck", "Deck"))))?;
+
+    match deck.delete(user_id) {
+        Ok(events) => Database::save_deck(&state.pool, deck.id, &events).await?,
+        Err(error) => match error {
+            deck::DeleteError::NotOwner => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Missing titles and words are treated as empty, and rejected by the domain.
+fn to_topics(topics: &[DeckTopicAttributes]) -> Vec<(String, Vec<String>)> {
+    topics
+        .iter()
+        .map(|topic| {
+            (
+                topic.title.clone().unwrap_or_default(),
+                topic.words.clone().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn empty_name() -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some("/data/attributes/name".to_string()),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some("Name must not be empty".to_string()),
+    }))
+}
+
+fn invalid_topic(index: usize, error: &deck::TopicError) -> ApiError {
+    let (field, detail) = match error {
+        deck::TopicError::DuplicateTitle => ("title", "Title must be unique within the deck"),
+        deck::TopicError::DuplicateWord => ("words", "Words must be unique"),
+        deck::TopicError::EmptyTitle => ("title", "Title must not be empty"),
+        deck::TopicError::EmptyWord => ("words", "Words must not be empty"),
+        deck::TopicError::WordCount => ("words", "Words must contain exactly 16 words"),
+    };
+
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some(format!("/data/attributes/topics/{index}/{field}")),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some(detail.to_string()),
+    }))
+}
+
+fn missing_topics() -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some("/data/attributes/topics".to_string()),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some("Topics must contain at least one topic".to_string()),
+    }))
+}
+
+fn owner_relationships(owner: user_id::UserId) -> Relationships {
+    Relationships(
+        [(
+            "owner".to_string(),
+            Relationship {
+                data: Some(ResourceIdentifiers::Individual(
+                    owner.to_resource_identifier(),
+                )),
+                links: Some(Links(
+                    [(
+                        "related".to_string(),
+                        format!("{}/{}", api_v1_users::PATH, owner.0),
+                    )]
+                    .into(),
+                )),
+            },
+        )]
+        .into(),
+    )
+}
+
+impl ToResource for deck::Deck {
+    const PATH: &'static str = PATH;
+
+    const TYPE: &'static str = TYPE;
+
+    type Attributes = DeckAttributes;
+
+    fn __attributes(&self) -> Option<Self::Attributes> {
+        Some(Self::Attributes {
+            name: Some(self.name.clone()),
+            topics: Some(
+                self.topics
+                    .iter()
+                    .map(|topic| DeckTopicAttributes {
+                        title: Some(topic.title.clone()),
+                        words: Some(topic.words().cloned().collect()),
+                    })
+                    .collect(),
+            ),
+        })
+    }
+
+    fn __id(&self) -> String {
+        self.id.0.to_string()
+    }
+
+    fn __relationships(&self) -> Option<Relationships> {
+        Some(owner_relationships(self.owner))
+    }
+}
+
+impl ToResource for deck::Query {
+    const PATH: &'static str = PATH;
+
+    const TYPE: &'static str = TYPE;
+
+    type Attributes = DeckAttributes;
+
+    fn __attributes(&self) -> Option<Self::Attributes> {
+        Some(Self::Attributes {
+            name: Some(self.name.clone()),
+            topics: None,
+        })
+    }
+
+    fn __id(&self) -> String {
+        self.id.0.to_string()
+    }
+
+    fn __relationships(&self) -> Option<Relationships> {
+        Some(owner_relationships(self.owner))
+    }
+}
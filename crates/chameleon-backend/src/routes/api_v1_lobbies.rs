@@ -3,29 +3,41 @@ use axum::{
     http::header::LOCATION,
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{get, patch, post},
+    routing::{delete, get, patch, post},
     Json, Router,
 };
 use chameleon_protocol::{
-    attributes::{ChatMessageAttributes, LobbyAttributes},
+    attributes::{
+        ChatMessageAttributes, ClueAttributes, GameAttributes, GamePlayerAttributes,
+        GuessAttributes, InviteAttributes, LobbyAttributes, LobbyFilter, MatchAttributes,
+        MatchStandingAttributes, RollAttributes, ScoreAttributes, VoteAttributes,
+    },
     jsonapi::{
         self, Links, Pagination, Relationship, Relationships, ResourceIdentifiers,
-        ResourceIdentifiersDocument, Resources, ResourcesDocument,
+        ResourceIdentifiersDocument, Resources, ResourcesDocument, Sort,
     },
+    topic,
 };
+use rand::Rng;
+use sqlx::{Pool, Postgres, Transaction};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 use crate::{
     app::AppState,
-    database::Database,
-    domain::{lobby, lobby_id, local_id, user_id},
+    database::{self, Database},
+    domain::{deck_id, game, invite_id, lobby, lobby_id, lobby_match, local_id, passcode, user_id},
     error::ApiError,
+    timer,
 };
 
-use super::{ToResource, ToResourceIdentifier, Variation};
+use super::{encode, page_links, ToResource, ToResourceIdentifier, Variation};
 
 pub const PATH: &str = "/api/v1/lobbies";
 const TYPE: &str = "lobby";
 
+/// Value of the `deck` attribute selecting the bundled default deck.
+const DEFAULT_DECK: &str = "default";
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(get_many))
@@ -42,14 +54,40 @@ pub fn router() -> Router<AppState> {
             "/:id/relationships/members",
             patch(update_relationships_members),
         )
+        .route(
+            "/:id/relationships/members",
+            delete(delete_relationships_members),
+        )
         .route("/:id/members", get(get_members))
+        // relationships: bans
+        .route("/:id/relationships/bans", get(get_relationships_bans))
+        .route("/:id/relationships/bans", post(create_relationships_bans))
+        .route("/:id/relationships/bans", delete(delete_relationships_bans))
+        .route("/:id/bans", get(get_bans))
+        // chat messages
+        .route("/:id/chat_messages", get(get_chat_messages))
+        // invites
+        .route("/:id/invites", get(get_invites))
+        .route("/:id/invites", post(create_invite))
+        .route("/:id/invites/:invite_id", get(get_invite))
+        .route("/:id/invites/:invite_id", delete(delete_invite))
+        // game
+        .route("/:id/game", get(get_game))
+        .route("/:id/matches", get(get_matches))
+        .route("/:id/scores", get(get_scores))
         // actions
         .route("/:id/actions/chat_message", post(actions_chat_message))
+        .route("/:id/actions/decide_tie", post(actions_decide_tie))
+        .route("/:id/actions/guess", post(actions_guess))
         .route("/:id/actions/join", post(actions_join))
         .route("/:id/actions/leave", post(actions_leave))
+        .route("/:id/actions/ready", post(actions_ready))
+        .route("/:id/actions/start_game", post(actions_start_game))
+        .route("/:id/actions/submit_clue", post(actions_submit_clue))
+        .route("/:id/actions/vote", post(actions_vote))
 }
 
-#[tracing::instrument(skip(state))]
+#[tracing::instrument(skip(state, document))]
 async fn create_one(
     State(state): State<AppState>,
     user_id: user_id::UserId,
@@ -83,12 +121,54 @@ async fn create_one(
         .try_get_attribute(|a| a.passcode.as_ref(), "passcode", "Passcode")
         .map(String::as_str);
 
-    let lobby = match lobby::Lobby::create(name, user_id, passcode.clone().ok(), require_passcode) {
+    let tie_break = try_get_tie_break(resource)?.unwrap_or_default();
+
+    let visibility = try_get_visibility(resource)?.unwrap_or_default();
+
+    let deck = try_get_deck(&state, resource).await?.unwrap_or_default();
+
+    let goal = try_get_goal(resource)?.unwrap_or_default();
+
+    let attributes = resource.attributes.as_ref();
+    let time_limits = lobby::TimeLimits {
+        clue: attributes
+            .and_then(|a| a.clue_time_limit)
+            .unwrap_or_default(),
+        vote: attributes
+            .and_then(|a| a.vote_time_limit)
+            .unwrap_or_default(),
+    };
+
+    let max_members = attributes
+        .and_then(|a| a.max_members)
+        .unwrap_or(lobby::MAX_MEMBERS);
+
+    let hash = match passcode {
+        Ok(passcode) => Some(passcode::Passcode::hash(passcode.to_string()).await),
+        Err(_) => None,
+    };
+
+    let settings = lobby::Settings {
+        deck,
+        goal,
+        max_members,
+        passcode: hash.as_ref(),
+        require_passcode,
+        tie_break,
+        time_limits,
+        visibility,
+    };
+
+    let lobby = match lobby::Lobby::create(name, user_id, settings) {
         Ok((lobby, events)) => {
             Database::save_lobby(&state.pool, lobby.id, &events).await?;
             lobby
         }
         Err(error) => match error {
+            lobby::CreateError::InvalidGoal => return Err(invalid_goal(goal)),
+            lobby::CreateError::InvalidMaxMembers => {
+                return Err(invalid_max_members(game::MIN_PLAYERS));
+            }
             lobby::CreateError::MissingPasscode => {
                 return Err(ApiError::JsonApi(passcode.unwrap_err()));
             }
@@ -135,44 +215,42 @@ async fn get_many(
     State(state): State<AppState>,
     local_id: local_id::LocalId,
     Query(pagination): Query<Pagination>,
+    Query(filter): Query<LobbyFilter>,
+    Query(sort): Query<Sort>,
 ) -> Result<Response, ApiError> {
-    let keyset_pagination = pagination.try_into()?;
-
-    let (lobbies, after) = Database::query_lobby(&state.pool, keyset_pagination).await?;
+    // filter and sort are carried over to the links
+    let query = [
+        ("filter[name]", &filter.name),
+        ("filter[require_passcode]", &filter.require_passcode),
+        ("filter[has_space]", &filter.has_space),
+        ("sort", &sort.sort),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some(format!("&{name}={}", encode(value.as_ref()?))))
+    .collect::<String>();
+
+    let lobby_pagination: database::LobbyPagination = pagination.try_into()?;
+    let lobby_filter: database::LobbyFilter = filter.try_into()?;
+    let lobby_sort: database::LobbySort = sort.try_into()?;
+
+    let page =
+        Database::query_lobby(&state.pool, &lobby_filter, &lobby_sort, &lobby_pagination).await?;
 
     let document = ResourcesDocument {
         data: Some(Resources::Collection(
-            lobbies
+            page.items
                 .iter()
                 .map(|lobby| lobby.to_resource(Variation::Nested))
                 .collect(),
         )),
         errors: None,
-        links: Some(Links(
-            [
-                (
-                    "self".to_string(),
-                    format!(
-                        "{PATH}?page[after]={}&page[size]={}",
-                        keyset_pagination.id, keyset_pagination.limit
-                    ),
-                ),
-                (
-                    "next".to_string(),
-                    format!(
-                        "{PATH}?page[after]={}&page[size]={}",
-                        after, keyset_pagination.limit
-                    ),
-                ),
-            ]
-            .into(),
-        )),
+        links: Some(page_links(PATH, &lobby_pagination, &page, &query)),
     };
 
     Ok((StatusCode::OK, Json(document)).into_response())
 }
 
-#[tracing::instrument(skip(state))]
+#[tracing::instrument(skip(state, document))]
 async fn update_one(
     State(state): State<AppState>,
     user_id: user_id::UserId,
@@ -181,10 +259,6 @@ async fn update_one(
 ) -> Result<Response, ApiError> {
     let resource = document.try_get_individual()?;
 
-    let mut lobby = Database::load_lobby(&state.pool, id)
-        .await?
-        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
-
     let name = resource
         .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
         .map(String::as_str);
@@ -197,18 +271,48 @@ async fn update_one(
             "require_passcode",
             "Require Passcode",
         )
-        .map(|x| *x);
-
-    match lobby.update(
-        user_id,
-        name.ok(),
-        passcode.clone().ok(),
-        require_passcode.ok(),
-    ) {
+        .copied();
+    let tie_break = try_get_tie_break(resource)?;
+    let visibility = try_get_visibility(resource)?;
+    let deck = try_get_deck(&state, resource).await?;
+    let goal = try_get_goal(resource)?;
+
+    let hash = match passcode {
+        Ok(passcode) => Some(passcode::Passcode::hash(passcode.to_string()).await),
+        Err(_) => None,
+    };
+
+    // the max members are checked against the members as of the lock
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut lobby = Database::load_lobby_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    let changes = lobby::Changes {
+        deck,
+        goal,
+        max_members: resource.attributes.as_ref().and_then(|a| a.max_members),
+        name: name.ok(),
+        passcode: hash.as_ref(),
+        require_passcode: require_passcode.ok(),
+        tie_break,
+        clue_time_limit: resource.attributes.as_ref().and_then(|a| a.clue_time_limit),
+        vote_time_limit: resource.attributes.as_ref().and_then(|a| a.vote_time_limit),
+        visibility,
+    };
+
+    match lobby.update(user_id, changes) {
         Ok(events) => {
-            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+            Database::apply_lobby_events(&mut transaction, lobby.id, &events).await?;
+            transaction.commit().await?;
         }
         Err(error) => match error {
+            lobby::UpdateError::InvalidGoal => return Err(invalid_goal(goal.unwrap_or_default())),
+            lobby::UpdateError::InvalidMaxMembers => {
+                let min = game::MIN_PLAYERS.max(lobby.members.len());
+                return Err(invalid_max_members(min));
+            }
             lobby::UpdateError::MissingPasscode => {
                 passcode?;
             }
@@ -259,13 +363,69 @@ async fn get_relationships_host(
     Ok((StatusCode::OK, Json(document)).into_response())
 }
 
-#[tracing::instrument(skip(_state))]
+#[tracing::instrument(skip(state))]
 async fn update_relationships_host(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     user_id: user_id::UserId,
     Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourceIdentifiersDocument>,
 ) -> Result<Response, ApiError> {
-    Ok(StatusCode::NOT_IMPLEMENTED.into_response())
+    let invalid_host = |detail: &str| {
+        ApiError::JsonApi(Box::new(jsonapi::Error {
+            status: 422,
+            source: Some(jsonapi::Source {
+                header: None,
+                parameter: None,
+                pointer: Some("/data/id".to_string()),
+            }),
+            title: Some("Invalid Field".to_string()),
+            detail: Some(detail.to_string()),
+        }))
+    };
+
+    let host = document
+        .try_get_individual("Host")?
+        .try_get_field(|a| a.id.as_ref(), "id", "Id")?
+        .parse()
+        .map(user_id::UserId)
+        .map_err(|_| invalid_host("Host must be a user id"))?;
+
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    match lobby.transfer_host(user_id, host) {
+        Ok(events) => {
+            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+        }
+        Err(error) => match error {
+            lobby::TransferHostError::NotHost => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            lobby::TransferHostError::NotMember => {
+                return Err(invalid_host("Host must be a member of the lobby"));
+            }
+        },
+    }
+
+    let document = ResourceIdentifiersDocument {
+        data: Some(ResourceIdentifiers::Individual(
+            lobby.get_host().to_resource_identifier(),
+        )),
+        errors: None,
+        links: Some(Links(
+            [
+                (
+                    "self".to_string(),
+                    format!("{PATH}/{}/relationships/host", lobby.id.0),
+                ),
+                ("related".to_string(), format!("{PATH}/{}/host", lobby.id.0)),
+            ]
+            .into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
 }
 
 #[tracing::instrument(skip(state))]
@@ -293,22 +453,88 @@ async fn get_host(
     Ok((StatusCode::OK, Json(document)).into_response())
 }
 
-#[tracing::instrument(skip(_state))]
+#[tracing::instrument(skip(state))]
 async fn get_relationships_members(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     local_id: local_id::LocalId,
     Path(id): Path<lobby_id::LobbyId>,
+    Query(pagination): Query<Pagination>,
 ) -> Result<Response, ApiError> {
-    Ok(StatusCode::NOT_IMPLEMENTED.into_response())
+    let keyset_pagination = pagination.try_into()?;
+
+    let page = Database::query_lobby_member(&state.pool, id, keyset_pagination).await?;
+
+    let mut links = page_links(
+        &format!("{PATH}/{}/relationships/members", id.0),
+        &keyset_pagination,
+        &page,
+        "",
+    );
+    links
+        .0
+        .insert("related".to_string(), format!("{PATH}/{}/members", id.0));
+
+    let document = ResourceIdentifiersDocument {
+        data: Some(ResourceIdentifiers::Collection(
+            page.items
+                .iter()
+                .map(|user| user.id.to_resource_identifier())
+                .collect(),
+        )),
+        errors: None,
+        links: Some(links),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
 }
 
-#[tracing::instrument(skip(_state))]
+/// Replace the members, members left out are kicked.
+#[tracing::instrument(skip(state))]
 async fn update_relationships_members(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourceIdentifiersDocument>,
+) -> Result<Response, ApiError> {
+    let members = try_get_user_ids(&document, "Members")?;
+
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    if !members.iter().all(|member| lobby.is_member(*member)) {
+        return Err(invalid_members("Members can only be removed"));
+    }
+
+    let kicked = lobby
+        .members
+        .iter()
+        .map(|member| member.user_id)
+        .filter(|member| !members.contains(member))
+        .collect::<Vec<_>>();
+
+    kick(&state, &mut lobby, user_id, &kicked).await?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Kick the members.
+#[tracing::instrument(skip(state))]
+async fn delete_relationships_members(
+    State(state): State<AppState>,
     user_id: user_id::UserId,
     Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourceIdentifiersDocument>,
 ) -> Result<Response, ApiError> {
-    Ok(StatusCode::NOT_IMPLEMENTED.into_response())
+    let members = try_get_user_ids(&document, "Members")?;
+
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    kick(&state, &mut lobby, user_id, &members).await?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
 }
 
 #[tracing::instrument(skip(state))]
@@ -320,34 +546,21 @@ async fn get_members(
 ) -> Result<Response, ApiError> {
     let keyset_pagination = pagination.try_into()?;
 
-    let (users, after) = Database::query_lobby_member(&state.pool, id, keyset_pagination).await?;
+    let page = Database::query_lobby_member(&state.pool, id, keyset_pagination).await?;
 
     let document = ResourcesDocument {
         data: Some(Resources::Collection(
-            users
+            page.items
                 .iter()
                 .map(|user| user.to_resource(Variation::Nested))
                 .collect(),
         )),
         errors: None,
-        links: Some(Links(
-            [
-                (
-                    "self".to_string(),
-                    format!(
-                        "{PATH}/{}/members?page[after]={}&page[size]={}",
-                        id.0, keyset_pagination.id, keyset_pagination.limit
-                    ),
-                ),
-                (
-                    "next".to_string(),
-                    format!(
-                        "{PATH}/{}/members?page[after]={after}&page[size]={}",
-                        id.0, keyset_pagination.limit
-                    ),
-                ),
-            ]
-            .into(),
+        links: Some(page_links(
+            &format!("{PATH}/{}/members", id.0),
+            &keyset_pagination,
+            &page,
+            "",
         )),
     };
 
@@ -355,137 +568,1486 @@ async fn get_members(
 }
 
 #[tracing::instrument(skip(state))]
-async fn actions_chat_message(
+async fn get_relationships_bans(
     State(state): State<AppState>,
     user_id: user_id::UserId,
     Path(id): Path<lobby_id::LobbyId>,
-    Json(document): Json<ResourcesDocument<ChatMessageAttributes>>,
+    Query(pagination): Query<Pagination>,
 ) -> Result<Response, ApiError> {
-    let message = document.try_get_attribute(|a| a.message.as_ref(), "message", "Message")?;
+    let keyset_pagination = pagination.try_into()?;
 
-    let mut lobby = Database::load_lobby(&state.pool, id)
+    let lobby = Database::load_lobby(&state.pool, id)
         .await?
         .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
 
-    match lobby.send_chat_message(user_id, message) {
-        Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
-        Err(error) => match error {
-            lobby::SendChatMessageError::NotMember => {
-                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())))
-            }
-        },
+    if lobby.get_host() != user_id {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let page = Database::query_lobby_ban(&state.pool, id, keyset_pagination).await?;
+
+    let mut links = page_links(
+        &format!("{PATH}/{}/relationships/bans", id.0),
+        &keyset_pagination,
+        &page,
+        "",
+    );
+    links
+        .0
+        .insert("related".to_string(), format!("{PATH}/{}/bans", id.0));
+
+    let document = ResourceIdentifiersDocument {
+        data: Some(ResourceIdentifiers::Collection(
+            page.items
+                .iter()
+                .map(|user| user.id.to_resource_identifier())
+                .collect(),
+        )),
+        errors: None,
+        links: Some(links),
     };
 
-    Ok((
-        StatusCode::ACCEPTED,
-        Json(ResourcesDocument {
-            data: Some(Resources::Individual(jsonapi::Resource {
-                id: None,
-                type_: Some("chat_message".to_string()),
-                attributes: Some(ChatMessageAttributes {
-                    message: Some(message.to_string()),
-                }),
-                links: None,
-                relationships: None,
-            })),
-            errors: None,
-            links: Some(Links(
-                [(
-                    "self".to_string(),
-                    format!("{PATH}/{}/actions/chat_message", lobby.id.0),
-                )]
-                .into(),
-            )),
-        }),
-    )
-        .into_response())
+    Ok((StatusCode::OK, Json(document)).into_response())
 }
 
+/// Ban the users, members among them are kicked.
 #[tracing::instrument(skip(state))]
-async fn actions_join(
+async fn create_relationships_bans(
     State(state): State<AppState>,
     user_id: user_id::UserId,
     Path(id): Path<lobby_id::LobbyId>,
-    Json(document): Json<ResourcesDocument<LobbyAttributes>>,
+    Json(document): Json<ResourceIdentifiersDocument>,
 ) -> Result<Response, ApiError> {
+    let bans = try_get_user_ids(&document, "Bans")?;
+
     let mut lobby = Database::load_lobby(&state.pool, id)
         .await?
         .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
 
-    let passcode = if lobby.require_passcode {
-        let passcode = document
-            .try_get_attribute(|a| a.passcode.as_ref(), "passcode", "Passcode")?
-            .as_str();
-        Some(passcode)
-    } else {
-        None
-    };
+    for ban in &bans {
+        if Database::load_user(&state.pool, *ban).await?.is_none() {
+            return Err(invalid_members("Bans must be existing users"));
+        }
+    }
 
-    match lobby.join(user_id, passcode) {
-        Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
+    match lobby.ban(user_id, &bans) {
+        Ok(events) => {
+            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+            remove_players(&state.pool, lobby.id, &bans).await?;
+        }
         Err(error) => match error {
-            lobby::JoinError::AlreadyJoined => {
-                // silently continue...
+            lobby::BanError::BanningSelf => {
+                return Err(invalid_members("The host cannot be banned"));
             }
-            lobby::JoinError::IncorrectPasscode => {
+            lobby::BanError::NotHost => {
                 return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
             }
         },
-    };
-
-    let document = ResourceIdentifiersDocument {
-        data: Some(ResourceIdentifiers::Individual(
-            user_id.to_resource_identifier(),
-        )),
-        errors: None,
-        links: Some(Links(
-            [
-                (
-                    "self".to_string(),
-                    format!("{PATH}/{}/relationships/members", lobby.id.0),
-                ),
-                (
-                    "related".to_string(),
-                    format!("{PATH}/{}/members", lobby.id.0),
-                ),
-            ]
-            .into(),
-        )),
-    };
+    }
 
-    Ok((StatusCode::OK, Json(document)).into_response())
+    Ok(StatusCode::NO_CONTENT.into_response())
 }
 
+/// Lift the bans.
 #[tracing::instrument(skip(state))]
-async fn actions_leave(
+async fn delete_relationships_bans(
     State(state): State<AppState>,
     user_id: user_id::UserId,
     Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourceIdentifiersDocument>,
 ) -> Result<Response, ApiError> {
+    let bans = try_get_user_ids(&document, "Bans")?;
+
     let mut lobby = Database::load_lobby(&state.pool, id)
         .await?
         .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
 
-    match lobby.leave(user_id) {
+    match lobby.unban(user_id, &bans) {
         Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
         Err(error) => match error {
-            lobby::LeaveError::NotMember => {
+            lobby::UnbanError::NotBanned => {
+                return Err(invalid_members("Bans must be banned users"));
+            }
+            lobby::UnbanError::NotHost => {
                 return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
             }
         },
-    };
+    }
 
-    let document = ResourceIdentifiersDocument {
-        data: None,
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_bans(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let keyset_pagination = pagination.try_into()?;
+
+    let lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    if lobby.get_host() != user_id {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let page = Database::query_lobby_ban(&state.pool, id, keyset_pagination).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            page.items
+                .iter()
+                .map(|user| user.to_resource(Variation::Nested))
+                .collect(),
+        )),
         errors: None,
-        links: None,
+        links: Some(page_links(
+            &format!("{PATH}/{}/bans", id.0),
+            &keyset_pagination,
+            &page,
+            "",
+        )),
     };
 
     Ok((StatusCode::OK, Json(document)).into_response())
 }
 
-impl ToResource for lobby::Lobby {
-    const PATH: &'static str = PATH;
+/// Chat history, oldest first.
+#[tracing::instrument(skip(state))]
+async fn get_chat_messages(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let keyset_pagination = pagination.try_into()?;
+
+    let lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    if !lobby.is_member(user_id) {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let page = Database::query_lobby_chat_message(&state.pool, id, keyset_pagination).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            page.items.iter().map(chat_message_resource).collect(),
+        )),
+        errors: None,
+        links: Some(page_links(
+            &format!("{PATH}/{}/chat_messages", id.0),
+            &keyset_pagination,
+            &page,
+            "",
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_invites(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let keyset_pagination = pagination.try_into()?;
+
+    let lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    if lobby.get_host() != user_id {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let page = Database::query_lobby_invite(&state.pool, id, keyset_pagination).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            page.items
+                .iter()
+                .map(|invite| invite_resource(id, invite))
+                .collect(),
+        )),
+        errors: None,
+        links: Some(page_links(
+            &format!("{PATH}/{}/invites", id.0),
+            &keyset_pagination,
+            &page,
+            "",
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+/// Mint an invite, by default for a single use within a day.
+#[tracing::instrument(skip(state))]
+async fn create_invite(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<InviteAttributes>>,
+) -> Result<Response, ApiError> {
+    let resource = document.try_get_individual()?;
+    let attributes = resource.attributes.as_ref();
+
+    let expires_at = attributes
+        .and_then(|a| a.expires_at.as_ref())
+        .map(|expires_at| {
+            OffsetDateTime::parse(expires_at, &Rfc3339)
+                .map_err(|_| invalid_attribute("expires_at", "Expires At must be RFC 3339"))
+        })
+        .transpose()?
+        .unwrap_or_else(|| OffsetDateTime::now_utc() + time::Duration::DAY);
+
+    let max_uses = attributes.and_then(|a| a.max_uses).unwrap_or(1);
+
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    let invite = match lobby.create_invite(user_id, expires_at, max_uses) {
+        Ok((invite, events)) => {
+            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+            invite
+        }
+        Err(error) => match error {
+            lobby::CreateInviteError::InvalidExpiry => {
+                return Err(invalid_attribute(
+                    "expires_at",
+                    "Expires At must be in the future",
+                ));
+            }
+            lobby::CreateInviteError::InvalidMaxUses => {
+                return Err(invalid_attribute("max_uses", "Max Uses must be positive"));
+            }
+            lobby::CreateInviteError::NotHost => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    };
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(invite_resource(id, &invite))),
+        errors: None,
+        links: None,
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [(LOCATION, format!("{PATH}/{}/invites/{}", id.0, invite.id.0))],
+        Json(document),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_invite(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path((id, invite_id)): Path<(lobby_id::LobbyId, invite_id::InviteId)>,
+) -> Result<Response, ApiError> {
+    let lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    if lobby.get_host() != user_id {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let invite = lobby
+        .invites
+        .iter()
+        .find(|invite| invite.id == invite_id)
+        .ok_or_else(|| {
+            ApiError::JsonApi(Box::new(jsonapi::Error::not_found("invite", "Invite")))
+        })?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(invite_resource(id, invite))),
+        errors: None,
+        links: None,
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+/// Revoke the invite, its redemptions are kept.
+#[tracing::instrument(skip(state))]
+async fn delete_invite(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path((id, invite_id)): Path<(lobby_id::LobbyId, invite_id::InviteId)>,
+) -> Result<Response, ApiError> {
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    match lobby.revoke_invite(user_id, invite_id) {
+        Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
+        Err(error) => match error {
+            lobby::RevokeInviteError::NotFound => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::not_found(
+                    "invite", "Invite",
+                ))));
+            }
+            lobby::RevokeInviteError::NotHost => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_game(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+) -> Result<Response, ApiError> {
+    let game = Database::load_game(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("game", "Game"))))?;
+
+    if !game.is_player(user_id) {
+        return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+    }
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_matches(
+    State(state): State<AppState>,
+    local_id: local_id::LocalId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let keyset_pagination = pagination.try_into()?;
+
+    let page = Database::query_lobby_match(&state.pool, id, keyset_pagination).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            page.items.iter().map(match_resource).collect(),
+        )),
+        errors: None,
+        links: Some(page_links(
+            &format!("{PATH}/{}/matches", id.0),
+            &keyset_pagination,
+            &page,
+            "",
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn get_scores(
+    State(state): State<AppState>,
+    local_id: local_id::LocalId,
+    Path(id): Path<lobby_id::LobbyId>,
+) -> Result<Response, ApiError> {
+    let scores = Database::query_lobby_score(&state.pool, id).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Collection(
+            scores
+                .iter()
+                .map(|score| jsonapi::Resource {
+                    id: Some(score.user_id.0.to_string()),
+                    type_: Some("score".to_string()),
+                    attributes: Some(ScoreAttributes {
+                        score: Some(score.score),
+                    }),
+                    links: None,
+                    relationships: None,
+                })
+                .collect(),
+        )),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/scores", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_chat_message(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<ChatMessageAttributes>>,
+) -> Result<Response, ApiError> {
+    let message = document.try_get_attribute(|a| a.message.as_ref(), "message", "Message")?;
+
+    send_chat_message(&state, id, user_id, message).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ResourcesDocument {
+            data: Some(Resources::Individual(jsonapi::Resource {
+                id: None,
+                type_: Some("chat_message".to_string()),
+                attributes: Some(ChatMessageAttributes {
+                    message: Some(message.clone()),
+                    user_id: Some(user_id.0.to_string()),
+                    created_at: None,
+                }),
+                links: None,
+                relationships: None,
+            })),
+            errors: None,
+            links: Some(Links(
+                [(
+                    "self".to_string(),
+                    format!("{PATH}/{}/actions/chat_message", id.0),
+                )]
+                .into(),
+            )),
+        }),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_decide_tie(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<VoteAttributes>>,
+) -> Result<Response, ApiError> {
+    let accused = try_get_accused(&document)?;
+
+    let game = decide_tie(&state, id, user_id, accused).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_guess(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<GuessAttributes>>,
+) -> Result<Response, ApiError> {
+    let word = document.try_get_attribute(|a| a.word.as_ref(), "word", "Word")?;
+
+    let game = guess(&state, id, user_id, word).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state, document))]
+async fn actions_join(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<LobbyAttributes>>,
+) -> Result<Response, ApiError> {
+    // the capacity is checked against the members as of the lock
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut lobby = Database::load_lobby_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    let invite = document
+        .try_get_attribute(|a| a.invite.as_ref(), "invite", "Invite")
+        .ok()
+        .map(|invite| {
+            invite
+                .parse()
+                .map(invite_id::InviteId)
+                .map_err(|_| invalid_attribute("invite", "Invite must be an invite token"))
+        })
+        .transpose()?;
+
+    // an invite stands in for the passcode
+    let passcode_verified = if lobby.require_passcode && invite.is_none() {
+        let passcode = document
+            .try_get_attribute(|a| a.passcode.as_ref(), "passcode", "Passcode")?
+            .clone();
+        match &lobby.passcode {
+            Some(hash) => hash.verify(passcode).await,
+            None => false,
+        }
+    } else {
+        false
+    };
+
+    match lobby.join(user_id, passcode_verified, invite) {
+        Ok(events) => {
+            match Database::apply_lobby_events(&mut transaction, lobby.id, &events).await {
+                // the uses are counted again as the redemption is inserted
+                Err(sqlx::Error::RowNotFound) => return Err(invalid_invite()),
+                result => result?,
+            }
+            transaction.commit().await?;
+        }
+        Err(error) => match error {
+            lobby::JoinError::AlreadyJoined => {
+                // silently continue...
+            }
+            lobby::JoinError::Banned => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error {
+                    detail: Some("You are banned from this lobby".to_string()),
+                    ..jsonapi::Error::forbidden()
+                })));
+            }
+            lobby::JoinError::Full => {
+                return Err(conflict("Lobby is full"));
+            }
+            lobby::JoinError::IncorrectPasscode => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            lobby::JoinError::InvalidInvite => {
+                return Err(invalid_invite());
+            }
+            lobby::JoinError::Private => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error {
+                    detail: Some("This lobby is joinable only by invitation".to_string()),
+                    ..jsonapi::Error::forbidden()
+                })));
+            }
+        },
+    }
+
+    let document = ResourceIdentifiersDocument {
+        data: Some(ResourceIdentifiers::Individual(
+            user_id.to_resource_identifier(),
+        )),
+        errors: None,
+        links: Some(Links(
+            [
+                (
+                    "self".to_string(),
+                    format!("{PATH}/{}/relationships/members", lobby.id.0),
+                ),
+                (
+                    "related".to_string(),
+                    format!("{PATH}/{}/members", lobby.id.0),
+                ),
+            ]
+            .into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_leave(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+) -> Result<Response, ApiError> {
+    leave(&state, id, user_id).await?;
+
+    let document = ResourceIdentifiersDocument {
+        data: None,
+        errors: None,
+        links: None,
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+/// Toggle whether the member is ready for the game to start.
+#[tracing::instrument(skip(state))]
+async fn actions_ready(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+) -> Result<Response, ApiError> {
+    toggle_ready(&state, id, user_id).await?;
+
+    let document = ResourceIdentifiersDocument {
+        data: None,
+        errors: None,
+        links: None,
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_start_game(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+) -> Result<Response, ApiError> {
+    let game = start_game(&state, id, user_id).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [(LOCATION, format!("{PATH}/{}/game", id.0))],
+        Json(document),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_submit_clue(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<ClueAttributes>>,
+) -> Result<Response, ApiError> {
+    let clue = document.try_get_attribute(|a| a.clue.as_ref(), "clue", "Clue")?;
+
+    let game = submit_clue(&state, id, user_id, clue).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+#[tracing::instrument(skip(state))]
+async fn actions_vote(
+    State(state): State<AppState>,
+    user_id: user_id::UserId,
+    Path(id): Path<lobby_id::LobbyId>,
+    Json(document): Json<ResourcesDocument<VoteAttributes>>,
+) -> Result<Response, ApiError> {
+    let accused = try_get_accused(&document)?;
+
+    let game = vote(&state, id, user_id, accused).await?;
+
+    let document = ResourcesDocument {
+        data: Some(Resources::Individual(game_resource(&game, user_id))),
+        errors: None,
+        links: Some(Links(
+            [("self".to_string(), format!("{PATH}/{}/game", id.0))].into(),
+        )),
+    };
+
+    Ok((StatusCode::OK, Json(document)).into_response())
+}
+
+fn conflict(detail: impl Into<String>) -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 409,
+        source: None,
+        title: Some("Conflict".to_string()),
+        detail: Some(detail.into()),
+    }))
+}
+
+/// Decide a tied vote, shared by the REST and WebSocket actions.
+pub(super) async fn decide_tie(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    accused: user_id::UserId,
+) -> Result<game::Game, ApiError> {
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let lobby = Database::load_lobby_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    let mut game = Database::load_game_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("game", "Game"))))?;
+
+    match game.decide_tie(&lobby, user_id, accused) {
+        Ok(events) => {
+            Database::apply_game_events(&mut transaction, id, game.id, &events).await?;
+            record_round(&mut transaction, id, &events).await?;
+            transaction.commit().await?;
+        }
+        Err(error) => match error {
+            game::DecideTieError::InvalidCandidate => {
+                return Err(invalid_accused("Accused must be one of the tied players"));
+            }
+            game::DecideTieError::NotHost => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            game::DecideTieError::WrongPhase => {
+                return Err(conflict("There is no tied vote to decide"));
+            }
+        },
+    }
+
+    Ok(game)
+}
+
+/// Guess the secret word, shared by the REST and WebSocket actions.
+pub(super) async fn guess(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    word: &str,
+) -> Result<game::Game, ApiError> {
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut game = Database::load_game_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("game", "Game"))))?;
+
+    match game.guess(user_id, word) {
+        Ok(events) => {
+            Database::apply_game_events(&mut transaction, id, game.id, &events).await?;
+            record_round(&mut transaction, id, &events).await?;
+            transaction.commit().await?;
+        }
+        Err(error) => match error {
+            game::GuessError::InvalidWord => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error {
+                    status: 422,
+                    source: Some(jsonapi::Source {
+                        header: None,
+                        parameter: None,
+                        pointer: Some("/data/attributes/word".to_string()),
+                    }),
+                    title: Some("Invalid Attribute".to_string()),
+                    detail: Some("Word must be one of the words on the card".to_string()),
+                })));
+            }
+            game::GuessError::NotChameleon => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            game::GuessError::WrongPhase => {
+                return Err(conflict("The chameleon has not been caught"));
+            }
+        },
+    }
+
+    Ok(game)
+}
+
+fn invalid_accused(detail: &str) -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some("/data/relationships/accused".to_string()),
+        }),
+        title: Some("Invalid Relationship".to_string()),
+        detail: Some(detail.to_string()),
+    }))
+}
+
+fn invalid_attribute(name: &str, detail: &str) -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some(format!("/data/attributes/{name}")),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some(detail.to_string()),
+    }))
+}
+
+fn invalid_goal(goal: lobby::Goal) -> ApiError {
+    let (pointer, detail) = match goal {
+        lobby::Goal::Rounds(_) => ("rounds", "Rounds must be at least 1"),
+        lobby::Goal::TargetScore(_) => ("target_score", "Target Score must be at least 1"),
+    };
+
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some(format!("/data/attributes/{pointer}")),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some(detail.to_string()),
+    }))
+}
+
+fn invalid_invite() -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        detail: Some("Invite is revoked, expired or used up".to_string()),
+        ..jsonapi::Error::forbidden()
+    }))
+}
+
+/// Max members outside of `min` and [`lobby::MAX_MEMBERS`].
+fn invalid_max_members(min: usize) -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some("/data/attributes/max_members".to_string()),
+        }),
+        title: Some("Invalid Attribute".to_string()),
+        detail: Some(format!(
+            "Max Members must be between {} and {}",
+            min,
+            lobby::MAX_MEMBERS
+        )),
+    }))
+}
+
+fn invalid_members(detail: &str) -> ApiError {
+    ApiError::JsonApi(Box::new(jsonapi::Error {
+        status: 422,
+        source: Some(jsonapi::Source {
+            header: None,
+            parameter: None,
+            pointer: Some("/data".to_string()),
+        }),
+        title: Some("Invalid Member".to_string()),
+        detail: Some(detail.to_string()),
+    }))
+}
+
+async fn kick(
+    state: &AppState,
+    lobby: &mut lobby::Lobby,
+    actor: user_id::UserId,
+    user_ids: &[user_id::UserId],
+) -> Result<(), ApiError> {
+    match lobby.kick(actor, user_ids) {
+        Ok(events) => {
+            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+            remove_players(&state.pool, lobby.id, user_ids).await?;
+        }
+        Err(error) => match error {
+            lobby::KickError::KickingSelf => {
+                return Err(invalid_members("The host cannot be kicked"));
+            }
+            lobby::KickError::NotHost => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            lobby::KickError::NotMember => {
+                return Err(invalid_members("Kicked users must be members of the lobby"));
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Remove the users who left the lobby from the running game.
+pub(crate) async fn remove_players(
+    pool: &Pool<Postgres>,
+    lobby_id: lobby_id::LobbyId,
+    user_ids: &[user_id::UserId],
+) -> Result<(), sqlx::Error> {
+    let mut transaction = Database::lock(pool, lobby_id).await?;
+
+    let Some(mut game) = Database::load_game_locked(&mut transaction, lobby_id).await? else {
+        return Ok(());
+    };
+
+    let mut events = Vec::new();
+    for user_id in user_ids {
+        match game.remove_player(*user_id) {
+            Ok(removed) => events.extend(removed),
+            Err(error) => match error {
+                game::RemovePlayerError::NotPlayer | game::RemovePlayerError::WrongPhase => {
+                    // silently continue...
+                }
+            },
+        }
+    }
+
+    Database::apply_game_events(&mut transaction, lobby_id, game.id, &events).await?;
+    record_round(&mut transaction, lobby_id, &events).await?;
+    transaction.commit().await?;
+    timer::schedule(pool, lobby_id, game.id, &events);
+
+    Ok(())
+}
+
+/// Record the round of the current match if the game events scored it, in the
+/// same transaction as the game events.
+pub(crate) async fn record_round(
+    transaction: &mut Transaction<'_, Postgres>,
+    lobby_id: lobby_id::LobbyId,
+    events: &[game::Events],
+) -> Result<(), sqlx::Error> {
+    if !events
+        .iter()
+        .any(|event| matches!(event, game::Events::Scored(_)))
+    {
+        return Ok(());
+    }
+
+    // games started before matches were introduced have no match to record
+    let Some(mut lobby_match) = Database::load_match_locked(transaction, lobby_id).await? else {
+        return Ok(());
+    };
+
+    match lobby_match.record_round() {
+        Ok(events) => {
+            Database::apply_match_events(transaction, lobby_id, lobby_match.id, &events).await?;
+        }
+        Err(error) => match error {
+            lobby_match::RecordRoundError::Finished => {
+                // silently continue...
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Leave the lobby, shared by the REST and WebSocket actions.
+pub(super) async fn leave(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+) -> Result<(), ApiError> {
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    match lobby.leave(user_id) {
+        Ok(events) => {
+            Database::save_lobby(&state.pool, lobby.id, &events).await?;
+            remove_players(&state.pool, lobby.id, &[user_id]).await?;
+        }
+        Err(error) => match error {
+            lobby::LeaveError::NotMember => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Send a chat message, shared by the REST and WebSocket actions.
+pub(super) async fn send_chat_message(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    message: &str,
+) -> Result<(), ApiError> {
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    match lobby.send_chat_message(user_id, message) {
+        Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
+        Err(error) => match error {
+            lobby::SendChatMessageError::NotMember => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())))
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Start the next game, shared by the REST and WebSocket actions.
+pub(super) async fn start_game(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+) -> Result<game::Game, ApiError> {
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut lobby = Database::load_lobby_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    // a deleted deck falls back to the default deck
+    let deck = match lobby.deck {
+        lobby::Deck::Default => topic::default_deck(),
+        lobby::Deck::Custom(deck_id) => Database::load_deck_locked(&mut transaction, deck_id)
+            .await?
+            .map_or_else(topic::default_deck, |deck| deck.topics),
+    };
+
+    let current = Database::load_game_locked(&mut transaction, lobby.id).await?;
+
+    // a finished match is followed by a new one, with fresh scores
+    let (lobby_match, match_events) =
+        match Database::load_match_locked(&mut transaction, lobby.id).await? {
+            Some(lobby_match) if !lobby_match.finished => (lobby_match, Vec::new()),
+            _ => lobby_match::Match::start(&lobby),
+        };
+
+    let result = {
+        let mut rng = rand::thread_rng();
+        match lobby.draw_topic(&deck, &mut rng) {
+            Ok((topic, mut lobby_events)) => game::Game::start(
+                &lobby,
+                &lobby_match,
+                current.as_ref(),
+                user_id,
+                topic,
+                rng.gen(),
+            )
+            .map(|(game, events)| {
+                lobby_events.extend(lobby.reset_ready());
+                (game, events, lobby_events)
+            }),
+            Err(error) => match error {
+                lobby::DrawTopicError::EmptyDeck => {
+                    return Err(conflict("The deck does not contain any topics"));
+                }
+            },
+        }
+    };
+
+    match result {
+        Ok((game, events, lobby_events)) => {
+            Database::apply_lobby_events(&mut transaction, lobby.id, &lobby_events).await?;
+            Database::apply_match_events(&mut transaction, lobby.id, lobby_match.id, &match_events)
+                .await?;
+            Database::apply_game_events(&mut transaction, lobby.id, game.id, &events).await?;
+            transaction.commit().await?;
+            timer::schedule(&state.pool, lobby.id, game.id, &events);
+            Ok(game)
+        }
+        Err(error) => match error {
+            game::StartError::GameInProgress => Err(conflict("A game is already in progress")),
+            game::StartError::MembersNotReady => {
+                Err(conflict("All members must be ready to start a game"))
+            }
+            game::StartError::NotEnoughPlayers => Err(conflict(format!(
+                "At least {} members are required to start a game",
+                game::MIN_PLAYERS
+            ))),
+            game::StartError::NotHost => {
+                Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())))
+            }
+        },
+    }
+}
+
+/// Submit a clue, shared by the REST and WebSocket actions.
+pub(super) async fn submit_clue(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    clue: &str,
+) -> Result<game::Game, ApiError> {
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut game = Database::load_game_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("game", "Game"))))?;
+
+    match game.submit_clue(user_id, clue) {
+        Ok(events) => {
+            Database::apply_game_events(&mut transaction, id, game.id, &events).await?;
+            transaction.commit().await?;
+            timer::schedule(&state.pool, id, game.id, &events);
+        }
+        Err(error) => match error {
+            game::SubmitClueError::AlreadySubmitted => {
+                return Err(conflict("A clue has already been submitted"));
+            }
+            game::SubmitClueError::InvalidClue => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error {
+                    status: 422,
+                    source: Some(jsonapi::Source {
+                        header: None,
+                        parameter: None,
+                        pointer: Some("/data/attributes/clue".to_string()),
+                    }),
+                    title: Some("Invalid Attribute".to_string()),
+                    detail: Some("Clue must be a single word".to_string()),
+                })));
+            }
+            game::SubmitClueError::NotPlayer => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            game::SubmitClueError::NotTurn => {
+                return Err(conflict("It is not your turn to submit a clue"));
+            }
+            game::SubmitClueError::WrongPhase => {
+                return Err(conflict("Clues are no longer being accepted"));
+            }
+        },
+    }
+
+    Ok(game)
+}
+
+/// Toggle whether the member is ready, shared by the REST and WebSocket actions.
+pub(super) async fn toggle_ready(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+) -> Result<(), ApiError> {
+    let mut lobby = Database::load_lobby(&state.pool, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("lobby", "Lobby"))))?;
+
+    match lobby.toggle_ready(user_id) {
+        Ok(events) => Database::save_lobby(&state.pool, lobby.id, &events).await?,
+        Err(error) => match error {
+            lobby::ToggleReadyError::NotMember => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn try_get_accused(
+    document: &ResourcesDocument<VoteAttributes>,
+) -> Result<user_id::UserId, ApiError> {
+    let id = document
+        .try_get_individual()?
+        .try_get_relationship("accused", "Accused")?
+        .try_get_resource_identifiers("accused")?
+        .try_get_individual("accused", "Accused")?
+        .try_get_field(|a| a.id.as_ref(), "id", "Id")?;
+
+    id.parse()
+        .map(user_id::UserId)
+        .map_err(|_| invalid_accused("Accused must be a user id"))
+}
+
+async fn try_get_deck(
+    state: &AppState,
+    resource: &jsonapi::Resource<LobbyAttributes>,
+) -> Result<Option<lobby::Deck>, ApiError> {
+    let invalid_deck = || {
+        ApiError::JsonApi(Box::new(jsonapi::Error {
+            status: 422,
+            source: Some(jsonapi::Source {
+                header: None,
+                parameter: None,
+                pointer: Some("/data/attributes/deck".to_string()),
+            }),
+            title: Some("Invalid Attribute".to_string()),
+            detail: Some(format!(
+                "Deck must be {DEFAULT_DECK} or an existing deck id"
+            )),
+        }))
+    };
+
+    let Some(deck) = resource.attributes.as_ref().and_then(|a| a.deck.as_ref()) else {
+        return Ok(None);
+    };
+
+    if deck == DEFAULT_DECK {
+        return Ok(Some(lobby::Deck::Default));
+    }
+
+    let deck_id = deck
+        .parse()
+        .map(deck_id::DeckId)
+        .map_err(|_| invalid_deck())?;
+
+    if Database::load_deck(&state.pool, deck_id).await?.is_none() {
+        return Err(invalid_deck());
+    }
+
+    Ok(Some(lobby::Deck::Custom(deck_id)))
+}
+
+fn try_get_goal(
+    resource: &jsonapi::Resource<LobbyAttributes>,
+) -> Result<Option<lobby::Goal>, ApiError> {
+    let attributes = resource.attributes.as_ref();
+    let rounds = attributes.and_then(|a| a.rounds);
+    let target_score = attributes.and_then(|a| a.target_score);
+
+    match (rounds, target_score) {
+        (Some(_), Some(_)) => Err(ApiError::JsonApi(Box::new(jsonapi::Error {
+            status: 422,
+            source: Some(jsonapi::Source {
+                header: None,
+                parameter: None,
+                pointer: Some("/data/attributes/target_score".to_string()),
+            }),
+            title: Some("Invalid Attribute".to_string()),
+            detail: Some("Rounds and Target Score must not both be set".to_string()),
+        }))),
+        (Some(rounds), None) => Ok(Some(lobby::Goal::Rounds(rounds))),
+        (None, Some(target_score)) => Ok(Some(lobby::Goal::TargetScore(target_score))),
+        (None, None) => Ok(None),
+    }
+}
+
+fn try_get_tie_break(
+    resource: &jsonapi::Resource<LobbyAttributes>,
+) -> Result<Option<lobby::TieBreak>, ApiError> {
+    resource
+        .attributes
+        .as_ref()
+        .and_then(|a| a.tie_break.as_ref())
+        .map(|tie_break| {
+            tie_break.parse().map_err(|()| {
+                ApiError::JsonApi(Box::new(jsonapi::Error {
+                    status: 422,
+                    source: Some(jsonapi::Source {
+                        header: None,
+                        parameter: None,
+                        pointer: Some("/data/attributes/tie_break".to_string()),
+                    }),
+                    title: Some("Invalid Attribute".to_string()),
+                    detail: Some("Tie Break must be one of host, revote".to_string()),
+                }))
+            })
+        })
+        .transpose()
+}
+
+/// User ids of a relationship document, `display` names the members in errors.
+fn try_get_user_ids(
+    document: &ResourceIdentifiersDocument,
+    display: &str,
+) -> Result<Vec<user_id::UserId>, ApiError> {
+    document
+        .try_get_collection(display)?
+        .iter()
+        .map(|resource| {
+            resource
+                .try_get_field(|a| a.id.as_ref(), "id", "Id")?
+                .parse()
+                .map(user_id::UserId)
+                .map_err(|_| invalid_members(&format!("{display} must be user ids")))
+        })
+        .collect()
+}
+
+fn try_get_visibility(
+    resource: &jsonapi::Resource<LobbyAttributes>,
+) -> Result<Option<lobby::Visibility>, ApiError> {
+    resource
+        .attributes
+        .as_ref()
+        .and_then(|a| a.visibility.as_ref())
+        .map(|visibility| {
+            visibility.parse().map_err(|()| {
+                ApiError::JsonApi(Box::new(jsonapi::Error {
+                    status: 422,
+                    source: Some(jsonapi::Source {
+                        header: None,
+                        parameter: None,
+                        pointer: Some("/data/attributes/visibility".to_string()),
+                    }),
+                    title: Some("Invalid Attribute".to_string()),
+                    detail: Some("Visibility must be one of private, public, unlisted".to_string()),
+                }))
+            })
+        })
+        .transpose()
+}
+
+/// Cast a vote, shared by the REST and WebSocket actions.
+pub(super) async fn vote(
+    state: &AppState,
+    id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    accused: user_id::UserId,
+) -> Result<game::Game, ApiError> {
+    let mut transaction = Database::lock(&state.pool, id).await?;
+
+    let mut game = Database::load_game_locked(&mut transaction, id)
+        .await?
+        .ok_or_else(|| ApiError::JsonApi(Box::new(jsonapi::Error::not_found("game", "Game"))))?;
+
+    match game.vote(user_id, accused) {
+        Ok(events) => {
+            Database::apply_game_events(&mut transaction, id, game.id, &events).await?;
+            record_round(&mut transaction, id, &events).await?;
+            transaction.commit().await?;
+            timer::schedule(&state.pool, id, game.id, &events);
+        }
+        Err(error) => match error {
+            game::VoteError::AlreadyVoted => {
+                return Err(conflict("A vote has already been cast"));
+            }
+            game::VoteError::InvalidCandidate => {
+                return Err(invalid_accused(
+                    "Accused must be another player who can be voted for",
+                ));
+            }
+            game::VoteError::NotPlayer => {
+                return Err(ApiError::JsonApi(Box::new(jsonapi::Error::forbidden())));
+            }
+            game::VoteError::WrongPhase => {
+                return Err(conflict("Votes are not being accepted"));
+            }
+        },
+    }
+
+    Ok(game)
+}
+
+fn chat_message_resource(
+    chat_message: &lobby::ChatMessage,
+) -> jsonapi::Resource<ChatMessageAttributes> {
+    jsonapi::Resource {
+        id: Some(chat_message.id.0.to_string()),
+        type_: Some("chat_message".to_string()),
+        attributes: Some(ChatMessageAttributes {
+            message: Some(chat_message.message.clone()),
+            user_id: Some(chat_message.user_id.0.to_string()),
+            created_at: Some(chat_message.created_at.format(&Rfc3339).unwrap()),
+        }),
+        links: None,
+        relationships: None,
+    }
+}
+
+/// Game as seen by the user.
+fn game_resource(game: &game::Game, user_id: user_id::UserId) -> jsonapi::Resource<GameAttributes> {
+    jsonapi::Resource {
+        id: Some(game.id.0.to_string()),
+        type_: Some("game".to_string()),
+        attributes: Some(GameAttributes {
+            topic: Some(game.topic.title.clone()),
+            words: Some(game.topic.words().cloned().collect()),
+            chameleon: Some(game.is_chameleon(user_id)),
+            secret_word: game.get_secret_word(user_id).map(ToString::to_string),
+            phase: Some(game.phase.as_str().to_string()),
+            turn: game.get_turn().map(|user_id| user_id.0.to_string()),
+            players: Some(
+                game.players
+                    .iter()
+                    .map(|player| GamePlayerAttributes {
+                        user_id: Some(player.user_id.0.to_string()),
+                        clue: player.clue.clone(),
+                        skipped: Some(player.skipped),
+                        departed: Some(player.departed),
+                        candidate: Some(player.candidate),
+                        voted: Some(player.vote.is_some()),
+                        // votes stay secret until revealed, except to the voter
+                        vote: (game.phase != game::Phase::Voting || player.user_id == user_id)
+                            .then_some(player.vote)
+                            .flatten()
+                            .map(|vote| vote.0.to_string()),
+                        // the chameleon is revealed once finished
+                        chameleon: (game.phase == game::Phase::Finished)
+                            .then_some(player.chameleon),
+                    })
+                    .collect(),
+            ),
+            tie_break: Some(game.tie_break.as_str().to_string()),
+            accused: game.accused.map(|accused| accused.0.to_string()),
+            guess: game.guess.clone(),
+            outcome: game
+                .get_outcome()
+                .map(|outcome| outcome.as_str().to_string()),
+            roll: game.get_roll(user_id).map(|roll| RollAttributes {
+                d6: Some(roll.d6),
+                d8: Some(roll.d8),
+            }),
+            coordinate: game
+                .get_secret_word(user_id)
+                .and_then(|_| topic::coordinate(game.secret_word)),
+            deadline: game
+                .deadline
+                .map(|deadline| deadline.format(&Rfc3339).unwrap()),
+        }),
+        links: None,
+        relationships: None,
+    }
+}
+
+fn invite_resource(
+    lobby_id: lobby_id::LobbyId,
+    invite: &lobby::Invite,
+) -> jsonapi::Resource<InviteAttributes> {
+    jsonapi::Resource {
+        id: Some(invite.id.0.to_string()),
+        type_: Some("invite".to_string()),
+        attributes: Some(InviteAttributes {
+            expires_at: Some(invite.expires_at.format(&Rfc3339).unwrap()),
+            max_uses: Some(invite.max_uses),
+            uses: Some(invite.uses),
+            revoked: Some(invite.revoked),
+        }),
+        links: Some(Links(
+            [(
+                "self".to_string(),
+                format!("{PATH}/{}/invites/{}", lobby_id.0, invite.id.0),
+            )]
+            .into(),
+        )),
+        relationships: None,
+    }
+}
+
+fn match_resource(lobby_match: &lobby_match::Match) -> jsonapi::Resource<MatchAttributes> {
+    jsonapi::Resource {
+        id: Some(lobby_match.id.0.to_string()),
+        type_: Some("match".to_string()),
+        attributes: Some(MatchAttributes {
+            rounds: lobby_match.goal.rounds(),
+            target_score: lobby_match.goal.target_score(),
+            rounds_played: Some(lobby_match.rounds_played),
+            finished: Some(lobby_match.finished),
+            // scores of the match in progress are under /scores
+            standings: lobby_match.finished.then(|| {
+                lobby_match
+                    .scores
+                    .iter()
+                    .map(|score| MatchStandingAttributes {
+                        user_id: Some(score.user_id.0.to_string()),
+                        score: Some(score.score),
+                    })
+                    .collect()
+            }),
+        }),
+        links: None,
+        relationships: None,
+    }
+}
+
+impl ToResource for lobby::Lobby {
+    const PATH: &'static str = PATH;
 
     const TYPE: &'static str = TYPE;
 
@@ -493,9 +2055,22 @@ impl ToResource for lobby::Lobby {
 
     fn __attributes(&self) -> Option<Self::Attributes> {
         Some(Self::Attributes {
-            name: Some(self.name.to_string()),
+            name: Some(self.name.clone()),
             passcode: None,
             require_passcode: Some(self.require_passcode),
+            tie_break: Some(self.tie_break.as_str().to_string()),
+            deck: Some(match self.deck {
+                lobby::Deck::Default => DEFAULT_DECK.to_string(),
+                lobby::Deck::Custom(deck_id) => deck_id.0.to_string(),
+            }),
+            rounds: self.goal.rounds(),
+            target_score: self.goal.target_score(),
+            clue_time_limit: Some(self.time_limits.clue),
+            vote_time_limit: Some(self.time_limits.vote),
+            max_members: Some(self.max_members),
+            member_count: Some(u32::try_from(self.members.len()).unwrap()),
+            visibility: Some(self.visibility.as_str().to_string()),
+            invite: None,
         })
     }
 
@@ -545,9 +2120,19 @@ impl ToResource for lobby::Query {
 
     fn __attributes(&self) -> Option<Self::Attributes> {
         Some(Self::Attributes {
-            name: Some(self.name.to_string()),
+            name: Some(self.name.clone()),
             passcode: None,
             require_passcode: Some(self.require_passcode),
+            tie_break: None,
+            deck: None,
+            rounds: None,
+            target_score: None,
+            clue_time_limit: None,
+            vote_time_limit: None,
+            max_members: Some(self.max_members),
+            member_count: Some(self.member_count),
+            visibility: None,
+            invite: None,
         })
     }
 
@@ -13,7 +13,7 @@ use chameleon_protocol::{
 use crate::{
     app::AppState,
     database::Database,
-    domain::{local_id, user, user_id},
+    domain::{lobby, local_id, user, user_id},
     error::ApiError,
 };
 
@@ -146,7 +146,33 @@ impl ToResource for user::User {
 
     fn __attributes(&self) -> Option<Self::Attributes> {
         Some(Self::Attributes {
-            name: Some(self.name.to_string()),
+            name: Some(self.name.clone()),
+            online: None,
+            ready: None,
+        })
+    }
+
+    fn __id(&self) -> String {
+        self.id.0.to_string()
+    }
+
+    fn __relationships(&self) -> Option<chameleon_protocol::jsonapi::Relationships> {
+        None
+    }
+}
+
+impl ToResource for lobby::MemberQuery {
+    const PATH: &'static str = PATH;
+
+    const TYPE: &'static str = TYPE;
+
+    type Attributes = UserAttributes;
+
+    fn __attributes(&self) -> Option<Self::Attributes> {
+        Some(Self::Attributes {
+            name: Some(self.name.clone()),
+            online: Some(self.online),
+            ready: Some(self.ready),
         })
     }
 
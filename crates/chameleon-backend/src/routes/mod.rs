@@ -1,5 +1,9 @@
 use chameleon_protocol::jsonapi::{Links, Relationships, Resource, ResourceIdentifier};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 
+use crate::database::{KeysetPagination, Page};
+
+pub mod api_v1_decks;
 pub mod api_v1_lobbies;
 pub mod api_v1_ping;
 pub mod api_v1_userinfo;
@@ -69,3 +73,50 @@ enum Variation {
     Nested,
     Root,
 }
+
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, NON_ALPHANUMERIC).to_string()
+}
+
+/// The `self`, `first`, `prev` and `next` links of a page, `prev` and `next` only if there are
+/// more results that way. The `query` is appended to every link as `&name=value` pairs.
+fn page_links<T, C>(
+    path: &str,
+    pagination: &KeysetPagination<C>,
+    page: &Page<T, C>,
+    query: &str,
+) -> Links
+where
+    C: std::fmt::Display,
+{
+    let link = |after: Option<&C>, before: Option<&C>| {
+        let after = after.map_or_else(String::new, |after| {
+            format!("page[after]={}&", encode(&after.to_string()))
+        });
+        let before = before.map_or_else(String::new, |before| {
+            format!("page[before]={}&", encode(&before.to_string()))
+        });
+        format!(
+            "{path}?{after}{before}page[size]={}{query}",
+            pagination.limit
+        )
+    };
+
+    let mut links = vec![
+        (
+            "self".to_string(),
+            link(pagination.after.as_ref(), pagination.before.as_ref()),
+        ),
+        ("first".to_string(), link(None, None)),
+    ];
+
+    if let Some(prev) = &page.prev {
+        links.push(("prev".to_string(), link(None, Some(prev))));
+    }
+
+    if let Some(next) = &page.next {
+        links.push(("next".to_string(), link(Some(next), None)));
+    }
+
+    Links(links.into_iter().collect())
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use axum::{
     extract::{
@@ -10,25 +10,34 @@ use axum::{
     Router,
 };
 use chameleon_protocol::{
-    frames::{LobbyFrame, LobbyRequest, LobbyResponse},
+    frames::{LobbyFrame, LobbyRequest, LobbyResponse, LobbyResync},
     jsonapi,
-    jsonrpc::FrameType,
+    jsonrpc::{self, FrameType, RequestFrame, ResponseFrame},
 };
 use futures::{
     stream::{SplitSink, SplitStream},
     SinkExt, StreamExt,
 };
+use tokio::{sync::broadcast::error::RecvError, time::Instant};
 use tracing::Instrument;
 
 use crate::{
     app::AppState,
-    database::Database,
+    database::{Database, LobbyNotification},
     domain::{lobby, lobby_id, local_id, user_id},
     error::ApiError,
 };
 
+use super::api_v1_lobbies;
+
 pub const PATH: &str = "/ws/v1/lobbies";
 
+/// Interval of the pings keeping the socket alive.
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Sockets silent for this long are closed.
+const IDLE_TIMEOUT: Duration = Duration::from_mins(1);
+
 pub fn router() -> Router<AppState> {
     Router::new().route("/:id", get(get_one))
 }
@@ -51,12 +60,8 @@ async fn get_one(
 async fn get_one_handler(app_state: AppState, lobby: lobby::Lobby, web_socket: WebSocket) {
     let (mut sink, mut stream) = web_socket.split();
 
-    let mut listener = Database::listener(&app_state.pool)
-        .await
-        .expect("TODO: Failed to get listener");
-
-    let user_id = match authentication(&app_state, &mut stream, &mut sink).await {
-        Ok(Some(user_id)) => user_id,
+    let (user_id, last_seen_seq) = match authentication(&app_state, &mut stream, &mut sink).await {
+        Ok(Some(authenticated)) => authenticated,
         Ok(None) => {
             return;
         }
@@ -79,33 +84,82 @@ async fn get_one_handler(app_state: AppState, lobby: lobby::Lobby, web_socket: W
         return;
     }
 
+    let mut subscription = app_state.hub.subscribe(lobby.id).await;
+
     tokio::spawn(
         async move {
-            listener
-                .listen(&format!("/lobbies/{}", lobby.id.0))
-                .await
-                .expect("TODO: failed to listen");
+            let connection = match app_state.presence.connect(lobby.id, user_id).await {
+                Ok(connection) => connection,
+                Err(error) => {
+                    tracing::error!(error =? error, "error");
+                    return;
+                }
+            };
+
+            // subscribed before replaying, live frames up to here were replayed already
+            let mut replayed = 0;
 
-            let mut notify_stream = listener.into_stream();
+            if let Some(last_seen_seq) = last_seen_seq {
+                match replay(&app_state, lobby.id, user_id, last_seen_seq, &mut sink).await {
+                    Ok(seq) => replayed = seq,
+                    Err(error) => {
+                        tracing::error!(error =? error, "error");
+                        return;
+                    }
+                }
+            }
+
+            let mut heartbeat = tokio::time::interval(PING_INTERVAL);
+            let mut last_seen = Instant::now();
 
             loop {
                 tokio::select! {
-                    message = notify_stream.next() => {
-                        let Some(Ok(message)) = message else {
-                            // stream closed
+                    _ = heartbeat.tick() => {
+                        if last_seen.elapsed() > IDLE_TIMEOUT {
+                            tracing::info!("idle timeout");
+                            sink.close().await.ok();
                             return;
-                        };
+                        }
 
-                        let Ok(frame) = LobbyFrame::try_from_str(message.payload()) else {
-                            // malformed frame
+                        if sink.send(Message::Ping(Vec::new())).await.is_err() {
                             return;
+                        }
+
+                        if let Err(error) = connection.renew().await {
+                            tracing::error!(error =? error, "error");
+                        }
+                    }
+                    notification = subscription.receiver.recv() => {
+                        let notification = match notification {
+                            Ok(notification) => notification,
+                            Err(RecvError::Lagged(skipped)) => {
+                                // the client reconnects to resynchronize
+                                tracing::warn!(skipped, "socket lagged behind");
+                                sink.close().await.ok();
+                                return;
+                            }
+                            Err(RecvError::Closed) => return,
                         };
 
-                        sink.send(Message::Text(
-                            frame.to_string().unwrap(),
-                        ))
-                        .await
-                        .unwrap();
+                        if !notification.is_addressed_to(user_id)
+                            || notification.seq().is_some_and(|seq| seq <= replayed)
+                        {
+                            continue;
+                        }
+
+                        if sink
+                            .send(Message::Text(notification.frame.to_string().unwrap()))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+
+                        if notification.is_kick_of(user_id) {
+                            // the kick is the final frame
+                            sink.close().await.ok();
+                            return;
+                        }
                     }
                     message = stream.next() => {
                         let Some(Ok(message)) = message else {
@@ -113,20 +167,49 @@ async fn get_one_handler(app_state: AppState, lobby: lobby::Lobby, web_socket: W
                             return;
                         };
 
+                        // any frame, pongs included, shows the client is alive
+                        last_seen = Instant::now();
+
                         match message {
                             Message::Text(text) => {
                                 match LobbyFrame::try_from_str(&text) {
                                     Ok(frame) => {
-                                        tracing::info!(frame =? frame, "frame received");
+                                        tracing::debug!(frame =? frame, "frame received");
+
+                                        let reply =
+                                            dispatch(&app_state, lobby.id, user_id, frame).await;
+                                        let Some(reply) = reply else {
+                                            continue;
+                                        };
+
+                                        let left = is_leave(&reply);
+
+                                        if sink
+                                            .send(Message::Text(reply.to_string().unwrap()))
+                                            .await
+                                            .is_err()
+                                        {
+                                            return;
+                                        }
+
+                                        if left {
+                                            // the leave is the final frame
+                                            sink.close().await.ok();
+                                            return;
+                                        }
                                     }
                                     Err(_) => {
-                                        sink.send(Message::Text(
-                                            LobbyFrame::parse_error().to_string().unwrap(),
-                                        ))
-                                        .await
-                                        .expect("TODO: Failed to resend error");
+                                        if sink
+                                            .send(Message::Text(
+                                                LobbyFrame::parse_error().to_string().unwrap(),
+                                            ))
+                                            .await
+                                            .is_err()
+                                        {
+                                            return;
+                                        }
                                     }
-                                };
+                                }
                             }
                             Message::Close(reason) => tracing::info!(reason =? reason, "close"),
                             _ => {}
@@ -139,11 +222,190 @@ async fn get_one_handler(app_state: AppState, lobby: lobby::Lobby, web_socket: W
     );
 }
 
+/// Reply to a frame of the member, responses sent by the client go unanswered.
+async fn dispatch(
+    app_state: &AppState,
+    lobby_id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    frame: LobbyFrame,
+) -> Option<LobbyFrame> {
+    match frame.type_ {
+        FrameType::Request(request) => {
+            Some(dispatch_request(app_state, lobby_id, user_id, request).await)
+        }
+        // the method is known but its params did not parse
+        FrameType::RequestMethodNotFound(request)
+            if jsonrpc::methods::<LobbyRequest>().contains(&request.method.as_str()) =>
+        {
+            Some(LobbyFrame::invalid_params(request.id))
+        }
+        FrameType::RequestMethodNotFound(request) => Some(LobbyFrame::method_not_found(request.id)),
+        FrameType::Response(_) => None,
+    }
+}
+
+/// Apply a request of the member through the same domain code as the REST actions.
+async fn dispatch_request(
+    app_state: &AppState,
+    lobby_id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    request: RequestFrame<LobbyRequest>,
+) -> LobbyFrame {
+    let id = request.id;
+
+    let result = match request.data {
+        LobbyRequest::Authenticate(_) => Ok(LobbyResponse::Authenticate(true)),
+        LobbyRequest::ChatMessage(params) => {
+            let Some(message) = params.message else {
+                return LobbyFrame::invalid_params(id);
+            };
+            api_v1_lobbies::send_chat_message(app_state, lobby_id, user_id, &message)
+                .await
+                .map(|()| LobbyResponse::ChatMessage(true))
+        }
+        LobbyRequest::DecideTie(params) => {
+            let Some(accused) = params.accused.and_then(|a| a.parse().ok()) else {
+                return LobbyFrame::invalid_params(id);
+            };
+            api_v1_lobbies::decide_tie(app_state, lobby_id, user_id, user_id::UserId(accused))
+                .await
+                .map(|_| LobbyResponse::DecideTie(true))
+        }
+        LobbyRequest::Guess(params) => {
+            let Some(word) = params.word else {
+                return LobbyFrame::invalid_params(id);
+            };
+            api_v1_lobbies::guess(app_state, lobby_id, user_id, &word)
+                .await
+                .map(|_| LobbyResponse::Guess(true))
+        }
+        LobbyRequest::Leave(_) => api_v1_lobbies::leave(app_state, lobby_id, user_id)
+            .await
+            .map(|()| LobbyResponse::Leave(true)),
+        LobbyRequest::Ready(_) => api_v1_lobbies::toggle_ready(app_state, lobby_id, user_id)
+            .await
+            .map(|()| LobbyResponse::Ready(true)),
+        LobbyRequest::StartGame(_) => api_v1_lobbies::start_game(app_state, lobby_id, user_id)
+            .await
+            .map(|_| LobbyResponse::StartGame(true)),
+        LobbyRequest::SubmitClue(params) => {
+            let Some(clue) = params.clue else {
+                return LobbyFrame::invalid_params(id);
+            };
+            api_v1_lobbies::submit_clue(app_state, lobby_id, user_id, &clue)
+                .await
+                .map(|_| LobbyResponse::SubmitClue(true))
+        }
+        LobbyRequest::Vote(params) => {
+            let Some(accused) = params.accused.and_then(|a| a.parse().ok()) else {
+                return LobbyFrame::invalid_params(id);
+            };
+            api_v1_lobbies::vote(app_state, lobby_id, user_id, user_id::UserId(accused))
+                .await
+                .map(|_| LobbyResponse::Vote(true))
+        }
+        // sent by the server only
+        LobbyRequest::ClueSubmitted(_)
+        | LobbyRequest::GameDealt(_)
+        | LobbyRequest::GamePhaseChanged(_)
+        | LobbyRequest::GamePlayerDeparted(_)
+        | LobbyRequest::GameStarted(_)
+        | LobbyRequest::HostChanged(_)
+        | LobbyRequest::MatchFinished(_)
+        | LobbyRequest::PhaseDeadline(_)
+        | LobbyRequest::PresenceChanged(_)
+        | LobbyRequest::ReadyChanged(_)
+        | LobbyRequest::Resync(_)
+        | LobbyRequest::RoundScored(_)
+        | LobbyRequest::UserJoined(_)
+        | LobbyRequest::UserKicked(_)
+        | LobbyRequest::UserLeft(_)
+        | LobbyRequest::VoteCast(_)
+        | LobbyRequest::VotesRevealed(_) => return LobbyFrame::method_not_found(id),
+    };
+
+    match result {
+        Ok(response) => LobbyFrame::new_response(id, response),
+        // the status of the REST action doubles as the error code
+        Err(ApiError::JsonApi(error)) => LobbyFrame::new_error(
+            id,
+            i64::from(error.status),
+            error.detail.or(error.title).unwrap_or_default(),
+        ),
+        Err(ApiError::Sqlx(error)) => {
+            tracing::error!(error =? error, "internal server error");
+            LobbyFrame::internal_error(id)
+        }
+    }
+}
+
+/// Is the reply to a successful leave
+fn is_leave(frame: &LobbyFrame) -> bool {
+    matches!(
+        &frame.type_,
+        FrameType::Response(ResponseFrame {
+            result: Some(LobbyResponse::Leave(true)),
+            ..
+        })
+    )
+}
+
+/// Send the frames addressed to the member after the sequence number, returning the last
+/// sequence number replayed. Frames trimmed from the log, or a sequence number the lobby never
+/// reached, cannot be replayed, the member is told to resynchronize from the resources instead.
+async fn replay(
+    app_state: &AppState,
+    lobby_id: lobby_id::LobbyId,
+    user_id: user_id::UserId,
+    last_seen_seq: i64,
+    sink: &mut SplitSink<WebSocket, Message>,
+) -> Result<i64, sqlx::Error> {
+    let notifications =
+        Database::query_lobby_event(&app_state.pool, lobby_id, last_seen_seq).await?;
+
+    let seq = match notifications.last().and_then(LobbyNotification::seq) {
+        Some(seq) => seq,
+        None => Database::query_lobby_event_seq(&app_state.pool, lobby_id).await?,
+    };
+
+    let trimmed = notifications
+        .first()
+        .and_then(LobbyNotification::seq)
+        .is_some_and(|first| first > last_seen_seq + 1);
+
+    if trimmed || last_seen_seq > seq {
+        let frame =
+            LobbyFrame::new_request(None, LobbyRequest::Resync(LobbyResync { seq: Some(seq) }));
+
+        // a closed socket ends the loop that follows
+        sink.send(Message::Text(frame.to_string().unwrap()))
+            .await
+            .ok();
+
+        return Ok(seq);
+    }
+
+    let mut replayed = last_seen_seq;
+
+    for notification in notifications {
+        replayed = notification.seq().unwrap_or(replayed);
+
+        if notification.is_addressed_to(user_id) {
+            // a closed socket ends the loop that follows
+            sink.send(Message::Text(notification.frame.to_string().unwrap()))
+                .await
+                .ok();
+        }
+    }
+
+    Ok(replayed)
+}
+
 async fn authentication(
     app_state: &AppState,
     stream: &mut SplitStream<WebSocket>,
     sink: &mut SplitSink<WebSocket, Message>,
-) -> Result<Option<user_id::UserId>, axum::Error> {
+) -> Result<Option<(user_id::UserId, Option<i64>)>, axum::Error> {
     while let Some(message) = stream.next().await {
         let Message::Text(text) = message? else {
             continue;
@@ -192,7 +454,7 @@ async fn authentication(
         ))
         .await
         .unwrap();
-        return Ok(Some(user_id));
+        return Ok(Some((user_id, request.last_seen_seq)));
     }
 
     Ok(None)
@@ -0,0 +1,89 @@
+use sqlx::{Pool, Postgres};
+use time::OffsetDateTime;
+
+use crate::{
+    database::Database,
+    domain::{game, game_id, lobby_id},
+    routes::api_v1_lobbies,
+};
+
+/// Schedule the deadlines set by the game events.
+pub fn schedule(
+    pool: &Pool<Postgres>,
+    lobby_id: lobby_id::LobbyId,
+    game_id: game_id::GameId,
+    events: &[game::Events],
+) {
+    for event in events {
+        if let game::Events::DeadlineChanged(game::DeadlineChangedEvent {
+            deadline: Some(deadline),
+            ..
+        }) = event
+        {
+            spawn(pool.clone(), lobby_id, game_id, *deadline);
+        }
+    }
+}
+
+/// Reschedule the pending deadlines, they are only kept in memory while waiting.
+pub async fn restore(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    for (lobby_id, game_id, deadline) in Database::query_game_deadline(pool).await? {
+        spawn(pool.clone(), lobby_id, game_id, deadline);
+    }
+
+    Ok(())
+}
+
+fn spawn(
+    pool: Pool<Postgres>,
+    lobby_id: lobby_id::LobbyId,
+    game_id: game_id::GameId,
+    deadline: OffsetDateTime,
+) {
+    tokio::spawn(async move {
+        // a deadline that has already passed expires right away
+        let duration = (deadline - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or_default();
+        tokio::time::sleep(duration).await;
+
+        if let Err(error) = expire(&pool, lobby_id, game_id, deadline).await {
+            tracing::error!(error =? error, "error");
+        }
+    });
+}
+
+/// Expire the game, unless it moved on since the deadline was set.
+async fn expire(
+    pool: &Pool<Postgres>,
+    lobby_id: lobby_id::LobbyId,
+    game_id: game_id::GameId,
+    deadline: OffsetDateTime,
+) -> Result<(), sqlx::Error> {
+    // checked under the lock, a vote or clue landing meanwhile moves the deadline
+    let mut transaction = Database::lock(pool, lobby_id).await?;
+
+    let Some(mut game) = Database::load_game_locked(&mut transaction, lobby_id).await? else {
+        return Ok(());
+    };
+
+    if game.id != game_id || game.deadline != Some(deadline) {
+        return Ok(());
+    }
+
+    match game.expire() {
+        Ok(events) => {
+            Database::apply_game_events(&mut transaction, lobby_id, game.id, &events).await?;
+            api_v1_lobbies::record_round(&mut transaction, lobby_id, &events).await?;
+            transaction.commit().await?;
+            schedule(pool, lobby_id, game.id, &events);
+        }
+        Err(error) => match error {
+            game::ExpireError::NoDeadline | game::ExpireError::NotExpired => {
+                // silently continue...
+            }
+        },
+    }
+
+    Ok(())
+}
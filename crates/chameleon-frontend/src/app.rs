@@ -21,14 +21,14 @@ pub fn App() -> Html {
                 <Switch<Route> render={|route| match route {
                     Route::MainMenu => html! {
                         <AuthenticationSwitch
-                            challenge={|_| html!{ <User /> }}
-                            render={|_| html!{ <MainMenu /> }}
+                            challenge={|()| html!{ <User /> }}
+                            render={|()| html!{ <MainMenu /> }}
                         />
                     },
                     Route::Browse =>  html !{
                         <AuthenticationSwitch
-                            challenge={|_| html!{ <User /> }}
-                            render={|_| html!{ <Browse /> }}
+                            challenge={|()| html!{ <User /> }}
+                            render={|()| html!{ <Browse /> }}
                         />
                     },
                     Route::Lobby { id } => {
@@ -36,10 +36,10 @@ pub fn App() -> Html {
                         let id2 = id1.clone();
                         html! {
                            <AuthenticationSwitch
-                                challenge={move |_| {
+                                challenge={move |()| {
                                     html!{ <UserInvite id={id1.clone()} /> }
                                 }}
-                                render={move |_| {
+                                render={move |()| {
                                     html!{ <Lobby id={id2.clone()} /> }
                                 }}
                             />
@@ -47,8 +47,8 @@ pub fn App() -> Html {
                     },
                     Route::Host =>  html !{
                         <AuthenticationSwitch
-                            challenge={|_| html!{ <User /> }}
-                            render={|_| html!{ <Host /> }}
+                            challenge={|()| html!{ <User /> }}
+                            render={|()| html!{ <Host /> }}
                         />
                     },
                     Route::NotFound => html !{
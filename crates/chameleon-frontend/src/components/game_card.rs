@@ -0,0 +1,58 @@
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub topic: AttrValue,
+
+    pub words: Vec<AttrValue>,
+
+    #[prop_or_default]
+    pub chameleon: Option<bool>,
+
+    #[prop_or_default]
+    pub secret_word: Option<AttrValue>,
+
+    /// present when the user may guess the secret word
+    #[prop_or_default]
+    pub onguess: Option<Callback<AttrValue>>,
+}
+
+#[function_component]
+pub fn GameCard(props: &Props) -> Html {
+    html! {
+        <div class="game-card">
+            <div class="game-card--topic">{ &props.topic }</div>
+            <div class="game-card--words">
+            {
+                props.words.iter().map(|word| {
+                    let onclick = props.onguess.clone().map(|onguess| {
+                        let word = word.clone();
+                        Callback::from(move |_: MouseEvent| onguess.emit(word.clone()))
+                    });
+                    html! {
+                        <div class={classes!(
+                            "game-card--word",
+                            (props.secret_word.as_ref() == Some(word)).then_some("game-card--word-secret")
+                        )}>
+                            if let Some(onclick) = onclick {
+                                <button {onclick}>{ word }</button>
+                            } else {
+                                { word }
+                            }
+                        </div>
+                    }
+                }).collect::<Html>()
+            }
+            </div>
+            <div class="game-card--role">
+            {
+                match props.chameleon {
+                    Some(true) => html! { { "you are the chameleon" } },
+                    Some(false) => html! { { "you are not the chameleon" } },
+                    None => html! {},
+                }
+            }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,36 @@
+use yew::prelude::*;
+
+use crate::hooks::input::use_input;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub disabled: bool,
+    pub onsubmit: Callback<AttrValue>,
+}
+
+#[function_component]
+pub fn GameClueInput(props: &Props) -> Html {
+    let clue = use_input(String::new().into());
+    let onsubmit = use_callback(
+        |event: SubmitEvent, (state, callback)| {
+            event.prevent_default();
+            callback.emit(state.to_string().into());
+        },
+        (clue.state.clone(), props.onsubmit.clone()),
+    );
+
+    html! {
+        <div class="game-clue-input">
+            <form onsubmit={onsubmit}>
+                <input
+                    disabled={props.disabled}
+                    ref={clue.node_ref}
+                    onchange={clue.callback}
+                    value={clue.state.to_string()} />
+                <button
+                    disabled={props.disabled}
+                    type="submit">{ "give clue" }</button>
+            </form>
+        </div>
+    }
+}
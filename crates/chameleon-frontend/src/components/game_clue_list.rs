@@ -0,0 +1,16 @@
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    #[prop_or_default]
+    pub children: Children,
+}
+
+#[function_component]
+pub fn GameClueList(props: &Props) -> Html {
+    html! {
+        <div class="game-clue-list">
+            { props.children.clone() }
+        </div>
+    }
+}
@@ -0,0 +1,57 @@
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    pub name: AttrValue,
+
+    #[prop_or_default]
+    pub clue: Option<AttrValue>,
+
+    #[prop_or_default]
+    pub turn: bool,
+
+    #[prop_or_default]
+    pub voted: bool,
+
+    /// name of the accused player, once votes are visible
+    #[prop_or_default]
+    pub vote: Option<AttrValue>,
+
+    #[prop_or_default]
+    pub accused: bool,
+
+    /// present when this player can be accused
+    #[prop_or_default]
+    pub onaccuse: Option<Callback<MouseEvent>>,
+}
+
+#[function_component]
+pub fn GameClueListItem(props: &Props) -> Html {
+    html! {
+        <div class={classes!(
+            "game-clue-list-item",
+            props.accused.then_some("game-clue-list-item--accused")
+        )}>
+            <div>
+                { &props.name }
+                { ": " }
+                {
+                    match (&props.clue, props.turn) {
+                        (Some(clue), _) => clue.clone(),
+                        (None, true) => "thinking...".into(),
+                        (None, false) => "".into(),
+                    }
+                }
+                if let Some(vote) = &props.vote {
+                    { " (voted " } { vote } { ")" }
+                } else if props.voted {
+                    { " (voted)" }
+                }
+                if let Some(onaccuse) = &props.onaccuse {
+                    { " " }
+                    <button onclick={onaccuse}>{ "accuse" }</button>
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,32 @@
+use gloo::timers::callback::Interval;
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct Props {
+    /// RFC 3339.
+    pub deadline: AttrValue,
+}
+
+#[function_component]
+pub fn GameCountdown(props: &Props) -> Html {
+    let now = use_state(js_sys::Date::now);
+
+    {
+        let now = now.clone();
+        use_effect_with_deps(
+            move |_| {
+                let interval = Interval::new(1_000, move || now.set(js_sys::Date::now()));
+                move || drop(interval)
+            },
+            props.deadline.clone(),
+        );
+    }
+
+    let remaining = (js_sys::Date::parse(&props.deadline) - *now) / 1_000.0;
+
+    html! {
+        <div class="game-countdown">
+            { "time left: " } { format!("{:.0}s", remaining.max(0.0).ceil()) }
+        </div>
+    }
+}
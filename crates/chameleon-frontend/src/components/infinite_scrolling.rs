@@ -11,14 +11,20 @@ pub fn InfiniteScrolling(props: &Props) -> Html {
     );
 
     let onscroll = use_callback(
-        |_, (callback, node_ref)| handle_onscroll(callback, node_ref),
-        (props.onscroll.clone(), node_ref.clone()),
+        |_, (callback, node_ref, finished)| {
+            if !finished {
+                handle_onscroll(callback, node_ref);
+            }
+        },
+        (props.onscroll.clone(), node_ref.clone(), props.finished),
     );
 
     html! {
         <div class="infinite-scrolling" {onscroll} ref={node_ref}>
             { props.children.clone() }
-            <button {onclick}>{ "load more" }</button>
+            if !props.finished {
+                <button {onclick}>{ "load more" }</button>
+            }
         </div>
     }
 }
@@ -28,6 +34,10 @@ pub struct Props {
     #[prop_or_default]
     pub children: Children,
 
+    /// There is nothing more to load.
+    #[prop_or_default]
+    pub finished: bool,
+
     #[prop_or_default]
     pub onclick: Callback<()>,
 
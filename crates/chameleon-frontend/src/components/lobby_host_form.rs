@@ -1,4 +1,4 @@
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[function_component]
@@ -8,6 +8,9 @@ pub fn LobbyHostForm(props: &Props) -> Html {
     let name = use_node_ref();
     let passcode = use_node_ref();
     let require_passcode = use_node_ref();
+    let tie_break = use_node_ref();
+    let deck = use_node_ref();
+    let visibility = use_node_ref();
 
     let onchange = {
         let state = state.clone();
@@ -18,14 +21,26 @@ pub fn LobbyHostForm(props: &Props) -> Html {
     };
 
     let onsubmit = use_callback(
-        move |event, (callback, name, passcode, require_passcode)| {
-            handle_form_submit(&event, callback, name, passcode, require_passcode);
+        move |event, (callback, name, passcode, require_passcode, tie_break, deck, visibility)| {
+            handle_form_submit(
+                &event,
+                callback,
+                name,
+                passcode,
+                require_passcode,
+                tie_break,
+                deck,
+                visibility,
+            );
         },
         (
             props.onsubmit.clone(),
             name.clone(),
             passcode.clone(),
             require_passcode.clone(),
+            tie_break.clone(),
+            deck.clone(),
+            visibility.clone(),
         ),
     );
 
@@ -52,6 +67,32 @@ pub fn LobbyHostForm(props: &Props) -> Html {
                         <input class="lobby-host-form--input" disabled={props.disabled} type="password" ref={passcode} />
                     </div>
                 }
+                <div class="lobby-host-form--input-group">
+                    <label class="lobby-host-form--label">{ "re-vote on tie:" }</label>
+                    <input
+                        class="lobby-host-form--input"
+                        disabled={props.disabled}
+                        type="checkbox"
+                        value="tie_break"
+                        ref={tie_break} />
+                </div>
+                <div class="lobby-host-form--input-group">
+                    <label class="lobby-host-form--label">{ "deck:" }</label>
+                    <input
+                        class="lobby-host-form--input"
+                        disabled={props.disabled}
+                        type="text"
+                        placeholder="default"
+                        ref={deck} />
+                </div>
+                <div class="lobby-host-form--input-group">
+                    <label class="lobby-host-form--label">{ "visibility:" }</label>
+                    <select class="lobby-host-form--input" disabled={props.disabled} ref={visibility}>
+                        <option value="public" selected=true>{ "public" }</option>
+                        <option value="unlisted">{ "unlisted" }</option>
+                        <option value="private">{ "private" }</option>
+                    </select>
+                </div>
                 <div class="lobby-host-form--input-group">
                     <button class="lobby-host-form--button" disabled={props.disabled} type="submit">{ "host" }</button>
                 </div>
@@ -83,12 +124,16 @@ fn handle_require_passcode_onchange(state: &UseStateHandle<State>, require_passc
     }
 }
 
+#[allow(clippy::too_many_arguments)] // reason = "one node ref per form input"
 fn handle_form_submit(
     event: &SubmitEvent,
     callback: &Callback<OnsubmitEvent>,
     name: &NodeRef,
     passcode: &NodeRef,
     require_passcode: &NodeRef,
+    tie_break: &NodeRef,
+    deck: &NodeRef,
+    visibility: &NodeRef,
 ) {
     event.prevent_default();
 
@@ -102,11 +147,29 @@ fn handle_form_submit(
     } else {
         None
     };
+    let tie_break = if tie_break.cast::<HtmlInputElement>().unwrap().checked() {
+        "revote".into()
+    } else {
+        "host".into()
+    };
+
+    let deck = Some(deck.cast::<HtmlInputElement>().unwrap().value())
+        .filter(|deck| !deck.is_empty())
+        .map(Into::into);
+
+    let visibility = visibility
+        .cast::<HtmlSelectElement>()
+        .unwrap()
+        .value()
+        .into();
 
     callback.emit(OnsubmitEvent {
         name,
         passcode,
         require_passcode,
+        tie_break,
+        deck,
+        visibility,
     });
 }
 
@@ -114,4 +177,8 @@ pub struct OnsubmitEvent {
     pub name: AttrValue,
     pub passcode: Option<AttrValue>,
     pub require_passcode: bool,
+    pub tie_break: AttrValue,
+    /// Deck id, the default deck if `None`.
+    pub deck: Option<AttrValue>,
+    pub visibility: AttrValue,
 }
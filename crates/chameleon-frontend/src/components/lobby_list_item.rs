@@ -10,7 +10,11 @@ pub fn LobbyListItem(props: &Props) -> Html {
     html! {
         <>
             <div class="lobby-list-item--name" onclick={onclick.clone()}>{ &props.name }</div>
-            <div class="lobby-list-item--players" onclick={onclick.clone()}></div>
+            <div class="lobby-list-item--players" onclick={onclick.clone()}>
+                if let (Some(member_count), Some(max_members)) = (props.member_count, props.max_members) {
+                    { format!("{member_count}/{max_members}") }
+                }
+            </div>
             <div class="lobby-list-item--locked" {onclick}>
                 if props.require_passcode {
                     { "🔒" }
@@ -28,6 +32,12 @@ pub struct Props {
 
     pub require_passcode: bool,
 
+    #[prop_or_default]
+    pub member_count: Option<u32>,
+
+    #[prop_or_default]
+    pub max_members: Option<u32>,
+
     #[prop_or_default]
     pub onclick: Callback<AttrValue>,
 }
@@ -4,6 +4,8 @@ use yew::prelude::*;
 pub struct Props {
     pub id: AttrValue,
     pub name: AttrValue,
+    pub online: bool,
+    pub ready: bool,
 }
 
 #[function_component]
@@ -11,6 +13,12 @@ pub fn LobbyMemberListItem(props: &Props) -> Html {
     html! {
         <div class="lobby-member-list-item">
             <div>{ &props.name }</div>
+            if !props.online {
+                <div>{ "offline" }</div>
+            }
+            if props.ready {
+                <div>{ "ready" }</div>
+            }
         </div>
     }
 }
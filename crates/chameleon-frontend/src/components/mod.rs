@@ -1,4 +1,9 @@
 pub mod authentication_switch;
+pub mod game_card;
+pub mod game_clue_input;
+pub mod game_clue_list;
+pub mod game_clue_list_item;
+pub mod game_countdown;
 pub mod infinite_scrolling;
 pub mod lobby_chat_input;
 pub mod lobby_chat_list;
@@ -10,6 +15,7 @@ pub mod lobby_list;
 pub mod lobby_list_item;
 pub mod lobby_member_list;
 pub mod lobby_member_list_item;
+pub mod lobby_search_input;
 pub mod navigation;
 pub mod theme_container;
 pub mod theme_picker;
@@ -1,7 +1,10 @@
 use std::rc::Rc;
 
 use chameleon_protocol::{
-    attributes::{ChatMessageAttributes, LobbyAttributes, UserAttributes},
+    attributes::{
+        ChatMessageAttributes, ClueAttributes, GameAttributes, GuessAttributes, InviteAttributes,
+        LobbyAttributes, ScoreAttributes, UserAttributes, VoteAttributes,
+    },
     jsonapi::{ResourceIdentifiersDocument, ResourcesDocument},
     openid_connect,
 };
@@ -66,6 +69,34 @@ impl NetworkState {
             .await
     }
 
+    pub async fn action_lobby_decide_tie(
+        &self,
+        id: &str,
+        document: &ResourcesDocument<VoteAttributes>,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/decide_tie"))
+            .authentication_headers()
+            .json(document)?
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
+    pub async fn action_lobby_guess(
+        &self,
+        id: &str,
+        document: &ResourcesDocument<GuessAttributes>,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/guess"))
+            .authentication_headers()
+            .json(document)?
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn action_lobby_join(
         &self,
         id: &str,
@@ -92,6 +123,58 @@ impl NetworkState {
             .await
     }
 
+    pub async fn action_lobby_ready(
+        &self,
+        id: &str,
+    ) -> Result<ResourceIdentifiersDocument, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/ready"))
+            .authentication_headers()
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
+    pub async fn action_lobby_start_game(
+        &self,
+        id: &str,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/start_game"))
+            .authentication_headers()
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
+    pub async fn action_lobby_submit_clue(
+        &self,
+        id: &str,
+        document: &ResourcesDocument<ClueAttributes>,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/submit_clue"))
+            .authentication_headers()
+            .json(document)?
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
+    pub async fn action_lobby_vote(
+        &self,
+        id: &str,
+        document: &ResourcesDocument<VoteAttributes>,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/actions/vote"))
+            .authentication_headers()
+            .json(document)?
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn create_lobby(
         &self,
         document: &ResourcesDocument<LobbyAttributes>,
@@ -105,6 +188,20 @@ impl NetworkState {
             .await
     }
 
+    pub async fn create_lobby_invite(
+        &self,
+        id: &str,
+        document: &ResourcesDocument<InviteAttributes>,
+    ) -> Result<ResourcesDocument<InviteAttributes>, gloo::net::Error> {
+        Request::post(&format!("/api/v1/lobbies/{id}/invites"))
+            .authentication_headers()
+            .json(document)?
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn create_user(
         &self,
         document: &ResourcesDocument<UserAttributes>,
@@ -130,6 +227,18 @@ impl NetworkState {
             .await
     }
 
+    pub async fn get_lobby_game(
+        &self,
+        id: &str,
+    ) -> Result<ResourcesDocument<GameAttributes>, gloo::net::Error> {
+        Request::get(&format!("/api/v1/lobbies/{id}/game"))
+            .authentication_headers()
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn get_lobby_host(
         &self,
         id: &str,
@@ -142,6 +251,19 @@ impl NetworkState {
             .await
     }
 
+    pub async fn get_lobby_chat_messages(
+        &self,
+        id: &str,
+        next: Option<String>,
+    ) -> Result<ResourcesDocument<ChatMessageAttributes>, gloo::net::Error> {
+        Request::get(&next.unwrap_or_else(|| format!("/api/v1/lobbies/{id}/chat_messages")))
+            .authentication_headers()
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn get_lobby_members(
         &self,
         id: &str,
@@ -155,6 +277,18 @@ impl NetworkState {
             .await
     }
 
+    pub async fn get_lobby_scores(
+        &self,
+        id: &str,
+    ) -> Result<ResourcesDocument<ScoreAttributes>, gloo::net::Error> {
+        Request::get(&format!("/api/v1/lobbies/{id}/scores"))
+            .authentication_headers()
+            .send()
+            .await?
+            .json()
+            .await
+    }
+
     pub async fn get_user(
         &self,
         id: &str,
@@ -183,13 +317,15 @@ impl NetworkState {
     pub async fn query_lobby(
         &self,
         next: Option<String>,
+        name: &str,
     ) -> Result<ResourcesDocument<LobbyAttributes>, gloo::net::Error> {
-        Request::get(&next.unwrap_or_else(|| "/api/v1/lobbies".to_string()))
-            .authentication_headers()
-            .send()
-            .await?
-            .json()
-            .await
+        let request = match next {
+            Some(next) => Request::get(&next),
+            None if name.is_empty() => Request::get("/api/v1/lobbies"),
+            None => Request::get("/api/v1/lobbies").query([("filter[name]", name)]),
+        };
+
+        request.authentication_headers().send().await?.json().await
     }
 
     #[allow(clippy::unused_self)]
@@ -243,7 +379,7 @@ impl NetworkState {
                     match message {
                         Some(message) => {
                             match sink.send(Message::Text(message)).await {
-                                Ok(_) => true,
+                                Ok(()) => true,
                                 Err(error) => {
                                     gloo::console::error!(format!("{error:?}"));
                                     false
@@ -261,7 +397,7 @@ impl NetworkState {
                                     match message {
                                         Message::Text(message) => {
                                             match rx_send.try_send(message) {
-                                                Ok(_) => true,
+                                                Ok(()) => true,
                                                 Err(error) => {
                                                     gloo::console::error!(format!("{error:?}"));
                                                     false
@@ -310,7 +446,7 @@ fn local_id() -> Result<String, StorageError> {
         Ok(value) => Ok(value),
         Err(StorageError::KeyNotFound(_)) => {
             match LocalStorage::set(KEY, Uuid::new_v4().to_string()) {
-                Ok(_) => LocalStorage::get(KEY),
+                Ok(()) => LocalStorage::get(KEY),
                 Err(err) => Err(err),
             }
         }
@@ -12,6 +12,7 @@ use crate::{
         lobby_details::{LobbyDetails, OnsubmitEvent},
         lobby_list::LobbyList,
         lobby_list_item::LobbyListItem,
+        lobby_search_input::LobbySearchInput,
         navigation::Navigation,
     },
     contexts::network::{NetworkContext, NetworkState},
@@ -27,9 +28,18 @@ pub fn Browse() -> Html {
         let network = network.clone();
         let state = state.clone();
         let rendered = state.rendered;
-        use_effect_with_deps(move |_| handle_first_render(&network, &state), rendered);
+        let search = state.search.clone();
+        use_effect_with_deps(
+            move |_| handle_first_render(&network, &state),
+            (rendered, search),
+        );
     }
 
+    let lobby_search_input_onsubmit = {
+        let state = state.clone();
+        use_callback(move |search, ()| state.dispatch(Action::Search(search)), ())
+    };
+
     let lobby_details_onsubmit = {
         let network = network.clone();
         let state = state.clone();
@@ -46,7 +56,7 @@ pub fn Browse() -> Html {
         let network = network.clone();
         let state = state.clone();
         use_callback(
-            move |id, _| handle_lobby_list_item_onclick(&network, &state, &id),
+            move |id, ()| handle_lobby_list_item_onclick(&network, &state, &id),
             (),
         )
     };
@@ -56,7 +66,7 @@ pub fn Browse() -> Html {
         let state = state.clone();
         let next = state.next_lobby_link.clone();
         use_callback(
-            move |_, _| handle_infinite_scrolling_onclick(&network, &state),
+            move |(), _| handle_infinite_scrolling_onclick(&network, &state),
             next,
         )
     };
@@ -76,8 +86,14 @@ pub fn Browse() -> Html {
             <div class="browse--grid-item-header">
                 <Navigation />
             </div>
+            <div class="browse--grid-item-search">
+                <LobbySearchInput
+                    disabled={state.networking}
+                    onsubmit={lobby_search_input_onsubmit} />
+            </div>
             <div class="browse--grid-item-browse">
                 <InfiniteScrolling
+                    finished={state.last_lobby_page}
                     onclick={infinite_scrolling_onclick}
                     onscroll={infinite_scrolling_onscroll}>
                     <LobbyList>
@@ -89,6 +105,8 @@ pub fn Browse() -> Html {
                                     id={&item.id}
                                     name={&item.name}
                                     require_passcode={item.require_passcode}
+                                    member_count={item.member_count}
+                                    max_members={item.max_members}
                                     onclick={lobby_list_item_onclick.clone()} />
                             }
                         }).collect::<Html>()
@@ -116,7 +134,9 @@ struct State {
     lobby: Option<StateLobbyDetails>,
     networking: bool,
     next_lobby_link: Option<AttrValue>,
+    last_lobby_page: bool,
     rendered: bool,
+    search: AttrValue,
 }
 
 impl Default for State {
@@ -126,7 +146,9 @@ impl Default for State {
             lobby: None,
             networking: false,
             next_lobby_link: None,
+            last_lobby_page: false,
             rendered: true,
+            search: AttrValue::default(),
         }
     }
 }
@@ -136,6 +158,8 @@ struct StateLobby {
     id: AttrValue,
     name: AttrValue,
     require_passcode: bool,
+    member_count: Option<u32>,
+    max_members: Option<u32>,
 }
 
 #[derive(Clone)]
@@ -155,12 +179,16 @@ impl Reducible for State {
                 let mut lobbies = self.lobbies.clone();
                 lobbies.extend(new_lobbies);
 
+                let last_lobby_page = next_link.is_none();
+
                 Rc::new(Self {
                     lobbies,
                     lobby: self.lobby.clone(),
                     networking: self.networking,
-                    next_lobby_link: Some(next_link),
+                    next_lobby_link: next_link,
+                    last_lobby_page,
                     rendered: true,
+                    search: self.search.clone(),
                 })
             }
             Action::LobbyDetails(lobby_details) => Rc::new(Self {
@@ -168,31 +196,47 @@ impl Reducible for State {
                 lobby: Some(lobby_details),
                 networking: self.networking,
                 next_lobby_link: self.next_lobby_link.clone(),
+                last_lobby_page: self.last_lobby_page,
                 rendered: self.rendered,
+                search: self.search.clone(),
             }),
             Action::Networking(networking) => Rc::new(Self {
                 lobbies: self.lobbies.clone(),
                 lobby: self.lobby.clone(),
                 networking,
                 next_lobby_link: self.next_lobby_link.clone(),
+                last_lobby_page: self.last_lobby_page,
                 rendered: self.rendered,
+                search: self.search.clone(),
             }),
             Action::Rendered => Rc::new(Self {
                 lobbies: self.lobbies.clone(),
                 lobby: self.lobby.clone(),
                 networking: self.networking,
                 next_lobby_link: self.next_lobby_link.clone(),
+                last_lobby_page: self.last_lobby_page,
                 rendered: true,
+                search: self.search.clone(),
+            }),
+            Action::Search(search) => Rc::new(Self {
+                lobbies: Vec::new(),
+                lobby: self.lobby.clone(),
+                networking: self.networking,
+                next_lobby_link: None,
+                last_lobby_page: false,
+                rendered: self.rendered,
+                search,
             }),
         }
     }
 }
 
 enum Action {
-    Lobbies((Vec<StateLobby>, AttrValue)),
+    Lobbies((Vec<StateLobby>, Option<AttrValue>)),
     LobbyDetails(StateLobbyDetails),
     Networking(bool),
     Rendered,
+    Search(AttrValue),
 }
 
 fn handle_first_render(network: &UseReducerHandle<NetworkState>, state: &UseReducerHandle<State>) {
@@ -260,6 +304,16 @@ fn action_join_lobby(
                     name: None,
                     passcode,
                     require_passcode: None,
+                    tie_break: None,
+                    deck: None,
+                    rounds: None,
+                    target_score: None,
+                    clue_time_limit: None,
+                    vote_time_limit: None,
+                    max_members: None,
+                    member_count: None,
+                    visibility: None,
+                    invite: None,
                 }),
                 links: None,
                 relationships: None,
@@ -286,7 +340,7 @@ fn action_join_lobby(
 }
 
 fn fetch_lobbies(network: &UseReducerHandle<NetworkState>, state: &UseReducerHandle<State>) {
-    if state.networking {
+    if state.networking || state.last_lobby_page {
         return;
     }
 
@@ -297,7 +351,7 @@ fn fetch_lobbies(network: &UseReducerHandle<NetworkState>, state: &UseReducerHan
     spawn_local(async move {
         let next = state.next_lobby_link.as_ref().map(ToString::to_string);
 
-        let response = match network.query_lobby(next).await {
+        let response = match network.query_lobby(next, &state.search).await {
             Ok(response) => response,
             Err(error) => {
                 state.dispatch(Action::Networking(false));
@@ -312,11 +366,11 @@ fn fetch_lobbies(network: &UseReducerHandle<NetworkState>, state: &UseReducerHan
             return;
         }
 
-        let next_link: AttrValue = response
+        // the last page has no next link
+        let next_link: Option<AttrValue> = response
             .try_get_link("next", "Next")
-            .expect("next link to be present")
-            .to_string()
-            .into();
+            .ok()
+            .map(|link| link.clone().into());
 
         let lobbies = response
             .try_get_collection_resources()
@@ -326,15 +380,12 @@ fn fetch_lobbies(network: &UseReducerHandle<NetworkState>, state: &UseReducerHan
                 let id = resource
                     .try_get_field(|a| a.id.as_ref(), "id", "Id")
                     .expect("Lobby to have ID")
-                    .to_string()
+                    .clone()
                     .into();
 
                 let name = resource
                     .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
-                    .map_or_else(
-                        |_| "<< UNNAMED >>".to_string().into(),
-                        |f| f.to_string().into(),
-                    );
+                    .map_or_else(|_| "<< UNNAMED >>".to_string().into(), |f| f.clone().into());
 
                 let require_passcode = resource
                     .try_get_attribute(
@@ -342,12 +393,16 @@ fn fetch_lobbies(network: &UseReducerHandle<NetworkState>, state: &UseReducerHan
                         "require_passcode",
                         "require_passcode",
                     )
-                    .map_or(false, |f| *f);
+                    .is_ok_and(|f| *f);
+
+                let attributes = resource.attributes.as_ref();
 
                 StateLobby {
                     id,
                     name,
                     require_passcode,
+                    member_count: attributes.and_then(|a| a.member_count),
+                    max_members: attributes.and_then(|a| a.max_members),
                 }
             })
             .collect();
@@ -402,22 +457,16 @@ fn fetch_lobby_details(
         let id: AttrValue = lobby_resource
             .try_get_field(|a| a.id.as_ref(), "id", "Id")
             .expect("Lobby to have ID")
-            .to_string()
+            .clone()
             .into();
 
         let host_name: AttrValue = host_resource
             .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
-            .map_or_else(
-                |_| "<< UNNAMED >>".to_string().into(),
-                |f| f.to_string().into(),
-            );
+            .map_or_else(|_| "<< UNNAMED >>".to_string().into(), |f| f.clone().into());
 
         let name: AttrValue = lobby_resource
             .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
-            .map_or_else(
-                |_| "<< UNNAMED >>".to_string().into(),
-                |f| f.to_string().into(),
-            );
+            .map_or_else(|_| "<< UNNAMED >>".to_string().into(), |f| f.clone().into());
 
         let require_passcode = lobby_resource
             .try_get_attribute(
@@ -425,7 +474,7 @@ fn fetch_lobby_details(
                 "require_passcode",
                 "require_passcode",
             )
-            .map_or(false, |f| *f);
+            .is_ok_and(|f| *f);
 
         state.dispatch(Action::LobbyDetails(StateLobbyDetails {
             id,
@@ -75,6 +75,16 @@ fn action_create_lobby(
                 name: Some(event.name.to_string()),
                 passcode: event.passcode.as_ref().map(ToString::to_string),
                 require_passcode: Some(event.require_passcode),
+                tie_break: Some(event.tie_break.to_string()),
+                deck: event.deck.as_ref().map(ToString::to_string),
+                rounds: None,
+                target_score: None,
+                clue_time_limit: None,
+                vote_time_limit: None,
+                max_members: None,
+                member_count: None,
+                visibility: Some(event.visibility.to_string()),
+                invite: None,
             }),
             links: None,
             relationships: None,
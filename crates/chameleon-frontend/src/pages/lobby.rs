@@ -1,4 +1,7 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use chameleon_protocol::{attributes, frames, jsonapi, jsonrpc};
 use futures::{channel::mpsc::Sender, SinkExt, StreamExt};
@@ -10,6 +13,8 @@ use yew_router::prelude::{use_navigator, Navigator};
 use crate::{
     app::Route,
     components::{
+        game_card::GameCard, game_clue_input::GameClueInput, game_clue_list::GameClueList,
+        game_clue_list_item::GameClueListItem, game_countdown::GameCountdown,
         lobby_chat_input::LobbyChatInput, lobby_chat_list::LobbyChatList,
         lobby_chat_list_item::LobbyChatListItem, lobby_member_list::LobbyMemberList,
         lobby_member_list_item::LobbyMemberListItem,
@@ -31,7 +36,54 @@ pub fn Lobby(props: &Props) -> Html {
     let onclick = {
         let id = props.id.clone();
         let network = network.clone();
-        use_callback(move |_, _| action_leave(&network, &navigator, &id), ())
+        use_callback(move |_, ()| action_leave(&network, &navigator, &id), ())
+    };
+
+    let start_game_onclick = {
+        let id = props.id.clone();
+        let network = network.clone();
+        use_callback(move |_, ()| action_start_game(&network, &id), ())
+    };
+
+    let ready_onclick = {
+        let id = props.id.clone();
+        let network = network.clone();
+        use_callback(move |_, ()| action_ready(&network, &id), ())
+    };
+
+    let invite_onclick = {
+        let id = props.id.clone();
+        let network = network.clone();
+        let state = state.clone();
+        use_callback(move |_, ()| action_create_invite(&network, &state, &id), ())
+    };
+
+    let clue_onsubmit = {
+        let id = props.id.clone();
+        let network = network.clone();
+        use_callback(
+            move |clue: AttrValue, ()| action_submit_clue(&network, &id, &clue),
+            (),
+        )
+    };
+
+    let accuse_onclick = {
+        let id = props.id.clone();
+        let network = network.clone();
+        let phase = present_game_phase(&state);
+        use_callback(
+            move |accused: AttrValue, phase| action_accuse(&network, &id, phase.as_ref(), &accused),
+            phase,
+        )
+    };
+
+    let guess_onclick = {
+        let id = props.id.clone();
+        let network = network.clone();
+        use_callback(
+            move |word: AttrValue, ()| action_guess(&network, &id, &word),
+            (),
+        )
     };
 
     let onsubmit = {
@@ -57,16 +109,86 @@ pub fn Lobby(props: &Props) -> Html {
                 <div>{ "=== lobby ===" }</div>
                 <div>{ "name: "} { present_lobby_name(&state) }</div>
                 <div><button onclick={onclick}>{ "leave" }</button></div>
+                <div><button onclick={ready_onclick}>{ "ready" }</button></div>
+                <div><button onclick={start_game_onclick}>{ "start game" }</button></div>
+                <div><button onclick={invite_onclick}>{ "create invite" }</button></div>
+                if let Some(invite_link) = &state.invite_link {
+                    <div>{ "invite link: " } { invite_link }</div>
+                }
             </div>
             <div class="lobby--grid-item-members">
                 <div>{ "=== lobby members ===" }</div>
                 <LobbyMemberList>
                 {
-                    present_members(&state).iter().map(|(id, name)| html! {
-                        <LobbyMemberListItem key={id.as_str()} id={id} name={name} />
+                    present_members(&state).iter().map(|(id, name, online, ready)| html! {
+                        <LobbyMemberListItem
+                            key={id.as_str()}
+                            id={id}
+                            name={name}
+                            online={*online}
+                            ready={*ready}
+                        />
                     }).collect::<Html>()
                 }
                 </LobbyMemberList>
+                <div>{ "=== scoreboard ===" }</div>
+                {
+                    present_scores(&state).into_iter().map(|(id, name, score)| html! {
+                        <div key={id.as_str()}>{ name } { ": " } { score }</div>
+                    }).collect::<Html>()
+                }
+            </div>
+            <div class="lobby--grid-item-game">
+                if let Some((topic, words, chameleon, secret_word)) = present_game(&state) {
+                    <GameCard
+                        {topic}
+                        {words}
+                        {chameleon}
+                        {secret_word}
+                        onguess={
+                            (chameleon == Some(true)
+                                && present_game_phase(&state).as_deref() == Some("guessing"))
+                                .then(|| guess_onclick.clone())
+                        } />
+                }
+                if let Some(roll) = present_roll(&state) {
+                    <div>{ "roll: " } { &roll }</div>
+                }
+                if let Some(phase) = present_game_phase(&state) {
+                    <div>{ "phase: " } { &phase }</div>
+                    if let Some(deadline) = present_deadline(&state) {
+                        <GameCountdown {deadline} />
+                    }
+                    <GameClueList>
+                    {
+                        present_players(&state).into_iter().map(|player| {
+                            let onaccuse = player.accusable.then(|| {
+                                let accuse_onclick = accuse_onclick.clone();
+                                let id = player.id.clone();
+                                Callback::from(move |_| accuse_onclick.emit(id.clone()))
+                            });
+                            html! {
+                                <GameClueListItem
+                                    key={player.id.as_str()}
+                                    name={player.name}
+                                    clue={player.clue}
+                                    turn={player.turn}
+                                    voted={player.voted}
+                                    vote={player.vote}
+                                    accused={player.accused}
+                                    {onaccuse} />
+                            }
+                        }).collect::<Html>()
+                    }
+                    </GameClueList>
+                    if let Some(revealed) = present_revealed_votes(&state) {
+                        <div>{ revealed }</div>
+                    }
+                    if let Some(outcome) = present_outcome(&state) {
+                        <div>{ outcome }</div>
+                    }
+                    <GameClueInput disabled={phase != "clues"} onsubmit={clue_onsubmit} />
+                }
             </div>
             <div class="lobby--grid-item-chat">
                 <LobbyChatList>
@@ -84,17 +206,35 @@ pub fn Lobby(props: &Props) -> Html {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct State {
     authenticated: bool,
 
+    // sequence number of the last frame received, replayed from on reconnect
+    last_seen_seq: Option<i64>,
+
+    game: Option<jsonapi::Resource<attributes::GameAttributes>>,
+
+    invite_link: Option<AttrValue>,
+
     lobby: Option<jsonapi::Resource<attributes::LobbyAttributes>>,
 
+    revealed_votes: Option<frames::LobbyVotesRevealed>,
+
     sender: Option<Sender<String>>,
 
     // members: user_id, name
     members: HashMap<AttrValue, AttrValue>,
 
+    // online: user_id
+    online: HashSet<AttrValue>,
+
+    // ready: user_id
+    ready: HashSet<AttrValue>,
+
+    // scores: user_id, score
+    scores: HashMap<AttrValue, u32>,
+
     // messages: key, user_id, content
     messages: Vec<(Uuid, AttrValue, AttrValue)>,
 
@@ -116,72 +256,98 @@ impl Reducible for State {
         match action {
             Action::Authenticated => Rc::new(Self {
                 authenticated: true,
-                lobby: self.lobby.clone(),
-                members: self.members.clone(),
-                messages: self.messages.clone(),
-                sender: self.sender.clone(),
-                status: self.status,
+                ..Self::clone(&self)
             }),
-            Action::ChatMessage(id, content) => {
+            Action::ChatMessage(key, id, content) => {
                 let mut messages = self.messages.clone();
-                messages.push((Uuid::new_v4(), id, content));
+                if !messages.iter().any(|message| message.0 == key) {
+                    messages.push((key, id, content));
+                }
                 Rc::new(Self {
-                    authenticated: self.authenticated,
-                    lobby: self.lobby.clone(),
-                    members: self.members.clone(),
                     messages,
-                    sender: self.sender.clone(),
-                    status: self.status,
+                    ..Self::clone(&self)
                 })
             }
             Action::Connected(sender) => Rc::new(Self {
-                authenticated: self.authenticated,
-                lobby: self.lobby.clone(),
-                members: self.members.clone(),
-                messages: self.messages.clone(),
                 sender: Some(sender),
-                status: self.status,
+                ..Self::clone(&self)
             }),
             Action::Disconnected => Rc::new(Self {
                 authenticated: false,
-                lobby: self.lobby.clone(),
-                members: self.members.clone(),
-                messages: self.messages.clone(),
                 sender: None,
-                status: self.status,
+                ..Self::clone(&self)
+            }),
+            Action::InviteCreated(invite_link) => Rc::new(Self {
+                invite_link: Some(invite_link),
+                ..Self::clone(&self)
+            }),
+            Action::PresenceChanged(id, online) => Rc::new(Self {
+                online: with_member(&self.online, id, online),
+                ..Self::clone(&self)
+            }),
+            Action::ReadyChanged(id, ready) => Rc::new(Self {
+                ready: with_member(&self.ready, id, ready),
+                ..Self::clone(&self)
+            }),
+            Action::Seen(seq) => Rc::new(Self {
+                last_seen_seq: Some(seq),
+                ..Self::clone(&self)
+            }),
+            Action::UserJoined | Action::UserLeft => self,
+            Action::VotesRevealed(revealed_votes) => Rc::new(Self {
+                revealed_votes: Some(revealed_votes),
+                ..Self::clone(&self)
             }),
-            Action::UserJoined(_) | Action::UserLeft(_) => self,
             Action::Status(status) => Rc::new(Self {
-                authenticated: self.authenticated,
-                lobby: self.lobby.clone(),
-                members: self.members.clone(),
-                messages: self.messages.clone(),
-                sender: self.sender.clone(),
                 status,
+                ..Self::clone(&self)
+            }),
+            Action::LoadedGame(game) => Rc::new(Self {
+                game,
+                ..Self::clone(&self)
             }),
             Action::LoadedLobby(lobby) => Rc::new(Self {
-                authenticated: self.authenticated,
                 lobby,
-                members: self.members.clone(),
-                messages: self.messages.clone(),
-                sender: self.sender.clone(),
-                status: self.status,
+                ..Self::clone(&self)
+            }),
+            Action::LoadedScores(scores) => Rc::new(Self {
+                scores: scores.unwrap_or_default().into_iter().collect(),
+                ..Self::clone(&self)
             }),
+            Action::LoadedMessages(history) => {
+                // messages received while the history loaded come after it
+                let mut messages = history;
+                for message in &self.messages {
+                    if !messages.iter().any(|m| m.0 == message.0) {
+                        messages.push(message.clone());
+                    }
+                }
+                Rc::new(Self {
+                    messages,
+                    ..Self::clone(&self)
+                })
+            }
             Action::LoadedMembers(members) => {
+                let members = members.unwrap_or_default();
+                let online = members
+                    .iter()
+                    .filter(|(_, _, online, _)| *online)
+                    .map(|(id, _, _, _)| id.clone())
+                    .collect::<HashSet<AttrValue>>();
+                let ready = members
+                    .iter()
+                    .filter(|(_, _, _, ready)| *ready)
+                    .map(|(id, _, _, _)| id.clone())
+                    .collect::<HashSet<AttrValue>>();
                 let members = members
-                    .map(|members| {
-                        members
-                            .into_iter()
-                            .collect::<HashMap<AttrValue, AttrValue>>()
-                    })
-                    .unwrap_or_default();
+                    .into_iter()
+                    .map(|(id, name, _, _)| (id, name))
+                    .collect::<HashMap<AttrValue, AttrValue>>();
                 Rc::new(Self {
-                    authenticated: self.authenticated,
-                    lobby: self.lobby.clone(),
                     members,
-                    messages: self.messages.clone(),
-                    sender: self.sender.clone(),
-                    status: self.status,
+                    online,
+                    ready,
+                    ..Self::clone(&self)
                 })
             }
         }
@@ -190,15 +356,85 @@ impl Reducible for State {
 
 enum Action {
     Authenticated,
-    // user id, content
-    ChatMessage(AttrValue, AttrValue),
+    // key, user id, content
+    ChatMessage(Uuid, AttrValue, AttrValue),
     Connected(Sender<String>),
-    UserJoined(AttrValue),
-    UserLeft(AttrValue),
+    InviteCreated(AttrValue),
+    // user id, online
+    PresenceChanged(AttrValue, bool),
+    // user id, ready
+    ReadyChanged(AttrValue, bool),
+    // sequence number
+    Seen(i64),
+    UserJoined,
+    UserLeft,
+    VotesRevealed(frames::LobbyVotesRevealed),
     Disconnected,
     Status(Status),
+    LoadedGame(Option<jsonapi::Resource<attributes::GameAttributes>>),
     LoadedLobby(Option<jsonapi::Resource<attributes::LobbyAttributes>>),
-    LoadedMembers(Option<Vec<(AttrValue, AttrValue)>>),
+    // key, user id, content
+    LoadedMessages(Vec<(Uuid, AttrValue, AttrValue)>),
+    // user id, name, online, ready
+    LoadedMembers(Option<Vec<(AttrValue, AttrValue, bool, bool)>>),
+    LoadedScores(Option<Vec<(AttrValue, u32)>>),
+}
+
+fn action_accuse(
+    network: &UseReducerHandle<NetworkState>,
+    id: &AttrValue,
+    phase: Option<&AttrValue>,
+    accused: &AttrValue,
+) {
+    let tie_break = phase.map(AttrValue::as_str) == Some("tie_break");
+
+    let document = jsonapi::ResourcesDocument {
+        data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
+            id: None,
+            type_: Some("vote".to_string()),
+            attributes: Some(attributes::VoteAttributes {}),
+            links: None,
+            relationships: Some(jsonapi::Relationships(
+                [(
+                    "accused".to_string(),
+                    jsonapi::Relationship {
+                        data: Some(jsonapi::ResourceIdentifiers::Individual(
+                            jsonapi::ResourceIdentifier {
+                                id: Some(accused.to_string()),
+                                type_: Some("user".to_string()),
+                            },
+                        )),
+                        links: None,
+                    },
+                )]
+                .into(),
+            )),
+        })),
+        errors: None,
+        links: None,
+    };
+
+    let id = id.clone();
+    let network = network.clone();
+    spawn_local(async move {
+        let response = if tie_break {
+            network.action_lobby_decide_tie(&id, &document).await
+        } else {
+            network.action_lobby_vote(&id, &document).await
+        };
+
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+        }
+    });
 }
 
 fn action_chat_message(
@@ -217,6 +453,8 @@ fn action_chat_message(
             type_: Some("chat_message".to_string()),
             attributes: Some(attributes::ChatMessageAttributes {
                 message: Some(content.to_string()),
+                user_id: None,
+                created_at: None,
             }),
             links: None,
             relationships: None,
@@ -235,6 +473,91 @@ fn action_chat_message(
     });
 }
 
+fn action_create_invite(
+    network: &UseReducerHandle<NetworkState>,
+    state: &UseReducerHandle<State>,
+    id: &AttrValue,
+) {
+    let document = jsonapi::ResourcesDocument {
+        data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
+            id: None,
+            type_: Some("invite".to_string()),
+            attributes: Some(attributes::InviteAttributes {
+                expires_at: None,
+                max_uses: None,
+                uses: None,
+                revoked: None,
+            }),
+            links: None,
+            relationships: None,
+        })),
+        errors: None,
+        links: None,
+    };
+
+    let id = id.clone();
+    let network = network.clone();
+    let state = state.clone();
+    spawn_local(async move {
+        let response = match network.create_lobby_invite(&id, &document).await {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        let invite = match response.try_get_field(|a| a.id.as_ref(), "id", "Id") {
+            Ok(invite) => invite.clone(),
+            Err(errors) => {
+                gloo::console::error!(format!("{errors:?}"));
+                return;
+            }
+        };
+
+        let origin = gloo::utils::window()
+            .location()
+            .origin()
+            .unwrap_or_default();
+
+        state.dispatch(Action::InviteCreated(
+            format!("{origin}/lobby/{id}?invite={invite}").into(),
+        ));
+    });
+}
+
+fn action_guess(network: &UseReducerHandle<NetworkState>, id: &AttrValue, word: &AttrValue) {
+    let document = jsonapi::ResourcesDocument {
+        data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
+            id: None,
+            type_: Some("guess".to_string()),
+            attributes: Some(attributes::GuessAttributes {
+                word: Some(word.to_string()),
+            }),
+            links: None,
+            relationships: None,
+        })),
+        errors: None,
+        links: None,
+    };
+
+    let id = id.clone();
+    let network = network.clone();
+    spawn_local(async move {
+        let response = match network.action_lobby_guess(&id, &document).await {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+        }
+    });
+}
+
 fn action_leave(network: &UseReducerHandle<NetworkState>, navigator: &Navigator, id: &AttrValue) {
     let id = id.clone();
     let navigator = navigator.clone();
@@ -245,6 +568,74 @@ fn action_leave(network: &UseReducerHandle<NetworkState>, navigator: &Navigator,
     });
 }
 
+fn action_ready(network: &UseReducerHandle<NetworkState>, id: &AttrValue) {
+    let id = id.clone();
+    let network = network.clone();
+    spawn_local(async move {
+        let response = match network.action_lobby_ready(&id).await {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+        }
+    });
+}
+
+fn action_start_game(network: &UseReducerHandle<NetworkState>, id: &AttrValue) {
+    let id = id.clone();
+    let network = network.clone();
+    spawn_local(async move {
+        let response = match network.action_lobby_start_game(&id).await {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+        }
+    });
+}
+
+fn action_submit_clue(network: &UseReducerHandle<NetworkState>, id: &AttrValue, clue: &AttrValue) {
+    let document = jsonapi::ResourcesDocument {
+        data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
+            id: None,
+            type_: Some("clue".to_string()),
+            attributes: Some(attributes::ClueAttributes {
+                clue: Some(clue.to_string()),
+            }),
+            links: None,
+            relationships: None,
+        })),
+        errors: None,
+        links: None,
+    };
+
+    let id = id.clone();
+    let network = network.clone();
+    spawn_local(async move {
+        let response = match network.action_lobby_submit_clue(&id, &document).await {
+            Ok(response) => response,
+            Err(error) => {
+                gloo::console::error!(error.to_string());
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+        }
+    });
+}
+
 fn load_data(
     network: &UseReducerHandle<NetworkState>,
     state: &UseReducerHandle<State>,
@@ -292,17 +683,107 @@ fn load_data(
                             .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
                             .unwrap();
 
-                        (id.to_string().into(), name.to_string().into())
+                        let online = member
+                            .try_get_attribute(|a| a.online.as_ref(), "online", "Online")
+                            .copied()
+                            .unwrap_or_default();
+
+                        let ready = member
+                            .try_get_attribute(|a| a.ready.as_ref(), "ready", "Ready")
+                            .copied()
+                            .unwrap_or_default();
+
+                        (id.clone().into(), name.clone().into(), online, ready)
                     })
                     .collect()
             });
 
         state.dispatch(Action::LoadedMembers(members));
 
+        let scores = network
+            .get_lobby_scores(&id)
+            .await
+            .unwrap_or_else(|_| jsonapi::ResourcesDocument::internal_server_error());
+
+        let scores = scores
+            .try_get_resources()
+            .and_then(jsonapi::Resources::try_get_collection)
+            .ok()
+            .map(|scores| {
+                scores
+                    .iter()
+                    .filter_map(|score| {
+                        let id = score.id.clone()?;
+                        let score = score.attributes.as_ref()?.score?;
+                        Some((id.into(), score))
+                    })
+                    .collect()
+            });
+
+        state.dispatch(Action::LoadedScores(scores));
+
+        let game = network
+            .get_lobby_game(&id)
+            .await
+            .unwrap_or_else(|_| jsonapi::ResourcesDocument::internal_server_error());
+
+        let game = game
+            .try_get_resources()
+            .and_then(jsonapi::Resources::try_get_individual)
+            .ok()
+            .cloned();
+
+        state.dispatch(Action::LoadedGame(game));
+
         state.dispatch(Action::Status(Status::Completed));
     });
 }
 
+/// Backfill the chat history, every page of it.
+fn load_messages(
+    network: &UseReducerHandle<NetworkState>,
+    state: &UseReducerHandle<State>,
+    props: &Props,
+) {
+    let id = props.id.clone();
+    let network = network.clone();
+    let state = state.clone();
+    spawn_local(async move {
+        let mut messages = Vec::new();
+        let mut next = None;
+
+        loop {
+            let response = match network.get_lobby_chat_messages(&id, next).await {
+                Ok(response) => response,
+                Err(error) => {
+                    gloo::console::error!(error.to_string());
+                    return;
+                }
+            };
+
+            if let Some(errors) = response.errors {
+                gloo::console::error!(format!("{errors:?}"));
+                return;
+            }
+
+            if let Some(jsonapi::Resources::Collection(page)) = response.data {
+                messages.extend(page.into_iter().filter_map(|message| {
+                    let key = Uuid::parse_str(message.id.as_ref()?).ok()?;
+                    let attributes = message.attributes?;
+                    Some((key, attributes.user_id?.into(), attributes.message?.into()))
+                }));
+            }
+
+            next = response.links.and_then(|mut links| links.0.remove("next"));
+            if next.is_none() {
+                break;
+            }
+        }
+
+        state.dispatch(Action::LoadedMessages(messages));
+    });
+}
+
 fn present_lobby_name(state: &State) -> AttrValue {
     state
         .lobby
@@ -311,17 +792,211 @@ fn present_lobby_name(state: &State) -> AttrValue {
             lobby
                 .try_get_attribute(|a| a.name.as_ref(), "name", "Name")
                 .ok()
-                .map(|v| v.to_string().into())
+                .map(|v| v.clone().into())
         })
         .unwrap_or_else(|| String::new().into())
 }
 
-/// presents members: user id, user name
-fn present_members(state: &State) -> Vec<(AttrValue, AttrValue)> {
+/// presents game: topic, words, chameleon, secret word
+#[allow(clippy::type_complexity)]
+fn present_game(
+    state: &State,
+) -> Option<(AttrValue, Vec<AttrValue>, Option<bool>, Option<AttrValue>)> {
+    let attributes = state.game.as_ref()?.attributes.as_ref()?;
+
+    Some((
+        attributes.topic.clone().unwrap_or_default().into(),
+        attributes
+            .words
+            .iter()
+            .flatten()
+            .map(|word| word.clone().into())
+            .collect(),
+        attributes.chameleon,
+        attributes.secret_word.clone().map(Into::into),
+    ))
+}
+
+/// presents game phase
+fn present_game_phase(state: &State) -> Option<AttrValue> {
+    let attributes = state.game.as_ref()?.attributes.as_ref()?;
+    attributes.phase.clone().map(Into::into)
+}
+
+/// presents deadline of the current clue turn, vote or tie break
+fn present_deadline(state: &State) -> Option<AttrValue> {
+    let attributes = state.game.as_ref()?.attributes.as_ref()?;
+    attributes.deadline.clone().map(Into::into)
+}
+
+/// presents roll of the dice and the coordinate it picks
+fn present_roll(state: &State) -> Option<AttrValue> {
+    let attributes = state.game.as_ref()?.attributes.as_ref()?;
+    let roll = attributes.roll.as_ref()?;
+
+    Some(
+        format!(
+            "d6 {}, d8 {} ({})",
+            roll.d6?,
+            roll.d8?,
+            attributes.coordinate.as_deref().unwrap_or_default()
+        )
+        .into(),
+    )
+}
+
+/// presents players in turn order
+fn present_players(state: &State) -> Vec<PresentedPlayer> {
+    let Some(attributes) = state
+        .game
+        .as_ref()
+        .and_then(|game| game.attributes.as_ref())
+    else {
+        return Vec::new();
+    };
+
+    let phase = attributes.phase.as_deref();
+
+    attributes
+        .players
+        .iter()
+        .flatten()
+        .filter_map(|player| {
+            let id: AttrValue = player.user_id.clone()?.into();
+            let candidate = player.candidate.unwrap_or_default();
+            Some(PresentedPlayer {
+                name: present_member_name(state, &id),
+                clue: player
+                    .clue
+                    .clone()
+                    .map(Into::into)
+                    .or_else(|| (player.departed == Some(true)).then(|| "(left)".into()))
+                    .or_else(|| (player.skipped == Some(true)).then(|| "(skipped)".into())),
+                turn: attributes.turn.as_deref() == Some(id.as_str()),
+                voted: player.voted.unwrap_or_default(),
+                vote: player
+                    .vote
+                    .as_ref()
+                    .map(|vote| present_member_name(state, &vote.clone().into())),
+                accused: attributes.accused.as_deref() == Some(id.as_str()),
+                accusable: candidate && matches!(phase, Some("voting" | "tie_break")),
+                id,
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::struct_excessive_bools)] // reason = "flags rendered by `GameClueListItem`"
+struct PresentedPlayer {
+    id: AttrValue,
+    name: AttrValue,
+    clue: Option<AttrValue>,
+    turn: bool,
+    voted: bool,
+    vote: Option<AttrValue>,
+    accused: bool,
+    accusable: bool,
+}
+
+/// presents outcome of a finished game as text
+fn present_outcome(state: &State) -> Option<AttrValue> {
+    let attributes = state.game.as_ref()?.attributes.as_ref()?;
+    let outcome = attributes.outcome.as_ref()?;
+
+    let chameleon = attributes
+        .players
+        .iter()
+        .flatten()
+        .find(|player| player.chameleon == Some(true))
+        .and_then(|player| player.user_id.clone())
+        .map(|user_id| present_member_name(state, &user_id.into()))
+        .unwrap_or_default();
+
+    let guess = attributes
+        .guess
+        .as_ref()
+        .map(|guess| format!(", guessed {guess}"))
+        .unwrap_or_default();
+
+    Some(format!("outcome: {outcome} (chameleon: {chameleon}{guess})").into())
+}
+
+/// presents scores of members, highest first: user id, user name, score
+fn present_scores(state: &State) -> Vec<(AttrValue, AttrValue, u32)> {
+    let mut scores = state
+        .members
+        .iter()
+        .map(|(id, name)| {
+            (
+                id.clone(),
+                name.clone(),
+                state.scores.get(id).copied().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    scores.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+    scores
+}
+
+/// presents revealed votes as text
+fn present_revealed_votes(state: &State) -> Option<AttrValue> {
+    let revealed = state.revealed_votes.as_ref()?;
+
+    let mut votes = revealed
+        .votes
+        .iter()
+        .flatten()
+        .map(|(user_id, accused)| {
+            format!(
+                "{} -> {}",
+                present_member_name(state, &user_id.clone().into()),
+                present_member_name(state, &accused.clone().into())
+            )
+        })
+        .collect::<Vec<_>>();
+    votes.sort();
+
+    let outcome = match &revealed.accused {
+        Some(accused) => format!(
+            "accused: {}",
+            present_member_name(state, &accused.clone().into())
+        ),
+        None => format!(
+            "tied: {}",
+            revealed
+                .tied
+                .iter()
+                .flatten()
+                .map(|user_id| present_member_name(state, &user_id.clone().into()).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    Some(format!("votes: {}; {outcome}", votes.join(", ")).into())
+}
+
+fn present_member_name(state: &State, user_id: &AttrValue) -> AttrValue {
+    state
+        .members
+        .get(user_id)
+        .cloned()
+        .unwrap_or_else(|| "???".to_string().into())
+}
+
+/// presents members: user id, user name, online, ready
+fn present_members(state: &State) -> Vec<(AttrValue, AttrValue, bool, bool)> {
     let mut members = state
         .members
         .iter()
-        .map(|(a, b)| (a.clone(), b.clone()))
+        .map(|(a, b)| {
+            (
+                a.clone(),
+                b.clone(),
+                state.online.contains(a),
+                state.ready.contains(a),
+            )
+        })
         .collect::<Vec<_>>();
     members.sort_by(|a, b| a.1.cmp(&b.1));
     members
@@ -344,6 +1019,84 @@ fn present_messages(state: &State) -> Vec<(Uuid, AttrValue, AttrValue, AttrValue
         .collect()
 }
 
+/// copy of the members with the member inserted or removed
+fn with_member(members: &HashSet<AttrValue>, id: AttrValue, contained: bool) -> HashSet<AttrValue> {
+    let mut members = members.clone();
+    if contained {
+        members.insert(id);
+    } else {
+        members.remove(&id);
+    }
+    members
+}
+
+/// Dispatch the state changes of a request sent by the server.
+fn dispatch_request(state: &UseReducerHandle<State>, request: frames::LobbyRequest) {
+    match request {
+        // sent by a client only
+        frames::LobbyRequest::Authenticate(_)
+        | frames::LobbyRequest::DecideTie(_)
+        | frames::LobbyRequest::Guess(_)
+        | frames::LobbyRequest::Leave(_)
+        | frames::LobbyRequest::Ready(_)
+        | frames::LobbyRequest::StartGame(_)
+        | frames::LobbyRequest::SubmitClue(_)
+        | frames::LobbyRequest::Vote(_) => {}
+        frames::LobbyRequest::ChatMessage(data) => {
+            state.dispatch(Action::ChatMessage(
+                data.id
+                    .and_then(|id| Uuid::parse_str(&id).ok())
+                    .unwrap_or_else(Uuid::new_v4),
+                data.user_id.unwrap().into(),
+                data.message.unwrap().into(),
+            ));
+        }
+        frames::LobbyRequest::ClueSubmitted(_)
+        | frames::LobbyRequest::GameDealt(_)
+        | frames::LobbyRequest::GamePhaseChanged(_)
+        | frames::LobbyRequest::GamePlayerDeparted(_)
+        | frames::LobbyRequest::GameStarted(_)
+        | frames::LobbyRequest::HostChanged(_)
+        | frames::LobbyRequest::MatchFinished(_)
+        | frames::LobbyRequest::PhaseDeadline(_)
+        | frames::LobbyRequest::RoundScored(_)
+        | frames::LobbyRequest::VoteCast(_) => {
+            state.dispatch(Action::Status(Status::Requested));
+        }
+        frames::LobbyRequest::PresenceChanged(data) => {
+            state.dispatch(Action::PresenceChanged(
+                data.user_id.unwrap().into(),
+                data.online.unwrap(),
+            ));
+        }
+        frames::LobbyRequest::ReadyChanged(data) => {
+            state.dispatch(Action::ReadyChanged(
+                data.user_id.unwrap().into(),
+                data.ready.unwrap(),
+            ));
+        }
+        frames::LobbyRequest::Resync(data) => {
+            // the missed frames are gone, the resources catch up instead
+            if let Some(seq) = data.seq {
+                state.dispatch(Action::Seen(seq));
+            }
+            state.dispatch(Action::Status(Status::Requested));
+        }
+        frames::LobbyRequest::UserJoined(_) => {
+            state.dispatch(Action::UserJoined);
+            state.dispatch(Action::Status(Status::Requested));
+        }
+        frames::LobbyRequest::UserKicked(_) | frames::LobbyRequest::UserLeft(_) => {
+            state.dispatch(Action::UserLeft);
+            state.dispatch(Action::Status(Status::Requested));
+        }
+        frames::LobbyRequest::VotesRevealed(data) => {
+            state.dispatch(Action::VotesRevealed(data));
+            state.dispatch(Action::Status(Status::Requested));
+        }
+    }
+}
+
 fn web_socket(
     network: UseReducerHandle<NetworkState>,
     state: UseReducerHandle<State>,
@@ -369,6 +1122,7 @@ fn web_socket(
             None,
             frames::LobbyRequest::Authenticate(frames::LobbyAuthenticate {
                 local_id: Some(network.local_id().expect("TODO: Failed to read local id")),
+                last_seen_seq: state.last_seen_seq,
             }),
         );
 
@@ -382,36 +1136,35 @@ fn web_socket(
                 .expect("TODO: Failed to deserialize frame");
 
             match frame.type_ {
-                jsonrpc::FrameType::Request(request) => match request.data {
-                    frames::LobbyRequest::Authenticate(_) => {}
-                    frames::LobbyRequest::ChatMessage(data) => {
-                        state.dispatch(Action::ChatMessage(
-                            data.user_id.unwrap().into(),
-                            data.message.unwrap().into(),
-                        ));
-                    }
-                    frames::LobbyRequest::UserJoined(data) => {
-                        state.dispatch(Action::UserJoined(data.user_id.unwrap().into()));
-                        state.dispatch(Action::Status(Status::Requested));
-                    }
-                    frames::LobbyRequest::UserLeft(data) => {
-                        state.dispatch(Action::UserLeft(data.user_id.unwrap().into()));
-                        state.dispatch(Action::Status(Status::Requested));
+                jsonrpc::FrameType::Request(request) => {
+                    if let Some(seq) = request.seq {
+                        state.dispatch(Action::Seen(seq));
                     }
-                },
-                jsonrpc::FrameType::Response(response) => match response.result {
-                    Some(result) => match result {
-                        frames::LobbyResponse::Authenticate(authenticated) => {
-                            if authenticated {
-                                state.dispatch(Action::Authenticated);
-                                load_data(&network, &state, &props);
+                    dispatch_request(&state, request.data);
+                }
+                jsonrpc::FrameType::Response(response) => {
+                    if let Some(result) = response.result {
+                        match result {
+                            frames::LobbyResponse::Authenticate(authenticated) => {
+                                if authenticated {
+                                    state.dispatch(Action::Authenticated);
+                                    load_data(&network, &state, &props);
+                                    load_messages(&network, &state, &props);
+                                }
                             }
+                            frames::LobbyResponse::ChatMessage(_)
+                            | frames::LobbyResponse::DecideTie(_)
+                            | frames::LobbyResponse::Guess(_)
+                            | frames::LobbyResponse::Leave(_)
+                            | frames::LobbyResponse::Ready(_)
+                            | frames::LobbyResponse::StartGame(_)
+                            | frames::LobbyResponse::SubmitClue(_)
+                            | frames::LobbyResponse::Vote(_) => {}
                         }
-                    },
-                    None => {}
-                },
+                    }
+                }
                 jsonrpc::FrameType::RequestMethodNotFound(_) => {}
-            };
+            }
         }
 
         state.dispatch(Action::Disconnected);
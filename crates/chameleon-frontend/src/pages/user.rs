@@ -39,7 +39,7 @@ fn Content() -> HtmlResult {
         let state = state.clone();
         use_callback(
             move |event, (user_id, _)| {
-                handle_onsubmit(&network, &current_user, &state, user_id, event);
+                handle_onsubmit(&network, &current_user, &state, user_id.as_ref(), event);
             },
             (user_id, networking),
         )
@@ -84,7 +84,7 @@ fn handle_onsubmit(
     network: &UseReducerHandle<NetworkState>,
     current_user: &UseReducerHandle<CurrentUserState>,
     state: &UseStateHandle<State>,
-    user_id: &Option<AttrValue>,
+    user_id: Option<&AttrValue>,
     event: OnsubmitEvent,
 ) {
     if state.networking {
@@ -96,7 +96,7 @@ fn handle_onsubmit(
     let current_user = current_user.clone();
     let network = network.clone();
     let state = state.clone();
-    let user_id = user_id.clone();
+    let user_id = user_id.cloned();
     spawn_local(async move {
         let document = jsonapi::ResourcesDocument {
             data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
@@ -104,6 +104,8 @@ fn handle_onsubmit(
                 type_: Some("user".to_string()),
                 attributes: Some(attributes::UserAttributes {
                     name: Some(event.name.to_string()),
+                    online: None,
+                    ready: None,
                 }),
                 links: None,
                 relationships: None,
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
+
 use chameleon_protocol::{attributes, jsonapi};
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
+use yew_router::prelude::use_location;
 
 use crate::{
-    components::user_form::{OnsubmitEvent, UserForm},
+    components::lobby_invite_form::{LobbyLandingForm, OnsubmitEvent},
     contexts::{
         current_user::{CurrentUserContext, CurrentUserState},
         network::{NetworkContext, NetworkState},
@@ -40,20 +43,32 @@ fn Content(props: &Props) -> HtmlResult {
     let user = use_current_user()?;
     let lobby = use_lobby(&props.id)?;
     let lobby_host = use_lobby_host(&props.id)?;
+    let location = use_location();
 
+    let invite = format_invite(location.as_ref());
     let lobby_name = format_lobby_name(&lobby);
     let lobby_host_name = format_user_name(&lobby_host);
+    let require_passcode = format_require_passcode(&lobby) && invite.is_none();
     let user_id = format_user_id(&user);
-    let user_name = format_user_name(&user);
+    let user_name = format_user_name(&user).unwrap_or_default();
 
     let onsubmit = {
+        let id = props.id.clone();
         let networking = state.networking;
         let state = state.clone();
         use_callback(
-            move |event, (user_id, _)| {
-                handle_onsubmit(&network, &current_user, &state, user_id, event);
+            move |event, (user_id, invite, _)| {
+                handle_onsubmit(
+                    &network,
+                    &current_user,
+                    &state,
+                    &id,
+                    user_id.as_ref(),
+                    invite.as_ref(),
+                    event,
+                );
             },
-            (user_id, networking),
+            (user_id, invite, networking),
         )
     };
 
@@ -65,7 +80,10 @@ fn Content(props: &Props) -> HtmlResult {
             <div>
                 {"Hosted by '"}{ lobby_host_name }{ "'" }
             </div>
-            <UserForm name={user_name} onsubmit={onsubmit} disabled={state.networking} />
+            <LobbyLandingForm
+                current_user_name={user_name}
+                {require_passcode}
+                {onsubmit} />
         </div>
     })
 }
@@ -80,6 +98,14 @@ struct State {
     networking: bool,
 }
 
+fn format_invite(location: Option<&yew_router::history::Location>) -> Option<AttrValue> {
+    location?
+        .query::<HashMap<String, String>>()
+        .ok()?
+        .remove("invite")
+        .map(Into::into)
+}
+
 fn format_lobby_name(
     lobby: &jsonapi::ResourcesDocument<attributes::LobbyAttributes>,
 ) -> Option<AttrValue> {
@@ -90,6 +116,20 @@ fn format_lobby_name(
         .map(Into::into)
 }
 
+fn format_require_passcode(
+    lobby: &jsonapi::ResourcesDocument<attributes::LobbyAttributes>,
+) -> bool {
+    lobby
+        .try_get_attribute(
+            |accessor| accessor.require_passcode.as_ref(),
+            "require_passcode",
+            "Require passcode",
+        )
+        .ok()
+        .copied()
+        .unwrap_or_default()
+}
+
 fn format_user_id(
     user: &jsonapi::ResourcesDocument<attributes::UserAttributes>,
 ) -> Option<AttrValue> {
@@ -112,7 +152,9 @@ fn handle_onsubmit(
     network: &UseReducerHandle<NetworkState>,
     current_user: &UseReducerHandle<CurrentUserState>,
     state: &UseStateHandle<State>,
-    user_id: &Option<AttrValue>,
+    id: &AttrValue,
+    user_id: Option<&AttrValue>,
+    invite: Option<&AttrValue>,
     event: OnsubmitEvent,
 ) {
     if state.networking {
@@ -124,14 +166,18 @@ fn handle_onsubmit(
     let current_user = current_user.clone();
     let network = network.clone();
     let state = state.clone();
-    let user_id = user_id.clone();
+    let id = id.clone();
+    let user_id = user_id.cloned();
+    let invite = invite.cloned();
     spawn_local(async move {
         let document = jsonapi::ResourcesDocument {
             data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
                 id: user_id.as_ref().map(ToString::to_string),
                 type_: Some("user".to_string()),
                 attributes: Some(attributes::UserAttributes {
-                    name: Some(event.name.to_string()),
+                    name: Some(event.user_name.to_string()),
+                    online: None,
+                    ready: None,
                 }),
                 links: None,
                 relationships: None,
@@ -159,6 +205,47 @@ fn handle_onsubmit(
             return;
         }
 
+        let document = jsonapi::ResourcesDocument {
+            data: Some(jsonapi::Resources::Individual(jsonapi::Resource {
+                id: None,
+                type_: Some("lobby".to_string()),
+                attributes: Some(attributes::LobbyAttributes {
+                    name: None,
+                    passcode: event.lobby_passcode.as_ref().map(ToString::to_string),
+                    require_passcode: None,
+                    tie_break: None,
+                    deck: None,
+                    rounds: None,
+                    target_score: None,
+                    clue_time_limit: None,
+                    vote_time_limit: None,
+                    max_members: None,
+                    member_count: None,
+                    visibility: None,
+                    invite: invite.as_ref().map(ToString::to_string),
+                }),
+                links: None,
+                relationships: None,
+            })),
+            errors: None,
+            links: None,
+        };
+
+        let response = match network.action_lobby_join(&id, &document).await {
+            Ok(response) => response,
+            Err(errors) => {
+                gloo::console::error!(format!("{errors:?}"));
+                state.set(State { networking: false });
+                return;
+            }
+        };
+
+        if let Some(errors) = response.errors {
+            gloo::console::error!(format!("{errors:?}"));
+            state.set(State { networking: false });
+            return;
+        }
+
         current_user.dispatch(CurrentUserState {
             authenticated: true,
         });
@@ -4,6 +4,151 @@ use serde::{Deserialize, Serialize};
 pub struct ChatMessageAttributes {
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+
+    /// Read-only.
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    /// Read-only, RFC 3339.
+    #[serde(rename = "created_at", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ClueAttributes {
+    #[serde(rename = "clue", skip_serializing_if = "Option::is_none")]
+    pub clue: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DeckAttributes {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(rename = "topics", skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<DeckTopicAttributes>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DeckTopicAttributes {
+    #[serde(rename = "title", skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(rename = "words", skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GameAttributes {
+    #[serde(rename = "topic", skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+
+    #[serde(rename = "words", skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<String>>,
+
+    #[serde(rename = "chameleon", skip_serializing_if = "Option::is_none")]
+    pub chameleon: Option<bool>,
+
+    #[serde(rename = "secret_word", skip_serializing_if = "Option::is_none")]
+    pub secret_word: Option<String>,
+
+    #[serde(rename = "phase", skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+
+    #[serde(rename = "turn", skip_serializing_if = "Option::is_none")]
+    pub turn: Option<String>,
+
+    #[serde(rename = "players", skip_serializing_if = "Option::is_none")]
+    pub players: Option<Vec<GamePlayerAttributes>>,
+
+    #[serde(rename = "tie_break", skip_serializing_if = "Option::is_none")]
+    pub tie_break: Option<String>,
+
+    #[serde(rename = "accused", skip_serializing_if = "Option::is_none")]
+    pub accused: Option<String>,
+
+    #[serde(rename = "guess", skip_serializing_if = "Option::is_none")]
+    pub guess: Option<String>,
+
+    #[serde(rename = "outcome", skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+
+    #[serde(rename = "roll", skip_serializing_if = "Option::is_none")]
+    pub roll: Option<RollAttributes>,
+
+    #[serde(rename = "coordinate", skip_serializing_if = "Option::is_none")]
+    pub coordinate: Option<String>,
+
+    /// When the current clue turn or vote runs out of time, RFC 3339.
+    #[serde(rename = "deadline", skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GamePlayerAttributes {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "clue", skip_serializing_if = "Option::is_none")]
+    pub clue: Option<String>,
+
+    #[serde(rename = "skipped", skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<bool>,
+
+    /// Left the lobby during the game.
+    #[serde(rename = "departed", skip_serializing_if = "Option::is_none")]
+    pub departed: Option<bool>,
+
+    #[serde(rename = "candidate", skip_serializing_if = "Option::is_none")]
+    pub candidate: Option<bool>,
+
+    #[serde(rename = "voted", skip_serializing_if = "Option::is_none")]
+    pub voted: Option<bool>,
+
+    #[serde(rename = "vote", skip_serializing_if = "Option::is_none")]
+    pub vote: Option<String>,
+
+    #[serde(rename = "chameleon", skip_serializing_if = "Option::is_none")]
+    pub chameleon: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GuessAttributes {
+    #[serde(rename = "word", skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InviteAttributes {
+    /// When the invite can no longer be redeemed, RFC 3339.
+    #[serde(rename = "expires_at", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+
+    #[serde(rename = "max_uses", skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+
+    /// Read-only.
+    #[serde(rename = "uses", skip_serializing_if = "Option::is_none")]
+    pub uses: Option<u32>,
+
+    /// Read-only.
+    #[serde(rename = "revoked", skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+}
+
+/// Filter query parameters of the lobby collection.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyFilter {
+    /// Case-insensitive part of the name.
+    #[serde(rename = "filter[name]")]
+    pub name: Option<String>,
+
+    #[serde(rename = "filter[require_passcode]")]
+    pub require_passcode: Option<String>,
+
+    /// Has fewer members than `max_members`.
+    #[serde(rename = "filter[has_space]")]
+    pub has_space: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -16,10 +161,101 @@ pub struct LobbyAttributes {
 
     #[serde(rename = "require_passcode", skip_serializing_if = "Option::is_none")]
     pub require_passcode: Option<bool>,
+
+    #[serde(rename = "tie_break", skip_serializing_if = "Option::is_none")]
+    pub tie_break: Option<String>,
+
+    #[serde(rename = "deck", skip_serializing_if = "Option::is_none")]
+    pub deck: Option<String>,
+
+    /// Rounds per match, exclusive with `target_score`.
+    #[serde(rename = "rounds", skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<u32>,
+
+    /// Score that ends a match, exclusive with `rounds`.
+    #[serde(rename = "target_score", skip_serializing_if = "Option::is_none")]
+    pub target_score: Option<u32>,
+
+    /// Seconds for each clue, 0 for no limit.
+    #[serde(rename = "clue_time_limit", skip_serializing_if = "Option::is_none")]
+    pub clue_time_limit: Option<u32>,
+
+    /// Seconds for voting, 0 for no limit.
+    #[serde(rename = "vote_time_limit", skip_serializing_if = "Option::is_none")]
+    pub vote_time_limit: Option<u32>,
+
+    #[serde(rename = "max_members", skip_serializing_if = "Option::is_none")]
+    pub max_members: Option<u32>,
+
+    /// Read-only.
+    #[serde(rename = "member_count", skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<u32>,
+
+    /// One of `public`, `unlisted` or `private`.
+    #[serde(rename = "visibility", skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+
+    /// Invite token redeemed on join, write-only.
+    #[serde(rename = "invite", skip_serializing_if = "Option::is_none")]
+    pub invite: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MatchAttributes {
+    #[serde(rename = "rounds", skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<u32>,
+
+    #[serde(rename = "target_score", skip_serializing_if = "Option::is_none")]
+    pub target_score: Option<u32>,
+
+    #[serde(rename = "rounds_played", skip_serializing_if = "Option::is_none")]
+    pub rounds_played: Option<u32>,
+
+    #[serde(rename = "finished", skip_serializing_if = "Option::is_none")]
+    pub finished: Option<bool>,
+
+    /// Final standings, highest score first.
+    #[serde(rename = "standings", skip_serializing_if = "Option::is_none")]
+    pub standings: Option<Vec<MatchStandingAttributes>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MatchStandingAttributes {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "score", skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RollAttributes {
+    #[serde(rename = "d6", skip_serializing_if = "Option::is_none")]
+    pub d6: Option<u8>,
+
+    #[serde(rename = "d8", skip_serializing_if = "Option::is_none")]
+    pub d8: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScoreAttributes {
+    #[serde(rename = "score", skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct UserAttributes {
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+
+    /// Read-only, only present on lobby members.
+    #[serde(rename = "online", skip_serializing_if = "Option::is_none")]
+    pub online: Option<bool>,
+
+    /// Read-only, only present on lobby members.
+    #[serde(rename = "ready", skip_serializing_if = "Option::is_none")]
+    pub ready: Option<bool>,
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct VoteAttributes {}
@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::jsonrpc::Frame;
 
 pub type LobbyFrame = Frame<LobbyRequest, LobbyResponse>;
 
+/// Methods of the lobby socket. Joining is left to the REST action, the socket is
+/// only open to members, so a `join` request is answered as an unknown method.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "method", content = "params")]
 pub enum LobbyRequest {
@@ -13,21 +17,94 @@ pub enum LobbyRequest {
     #[serde(rename = "chat_message")]
     ChatMessage(LobbyChatMessage),
 
+    #[serde(rename = "clue_submitted")]
+    ClueSubmitted(LobbyClueSubmitted),
+
+    #[serde(rename = "decide_tie")]
+    DecideTie(LobbyDecideTie),
+
+    #[serde(rename = "game_dealt")]
+    GameDealt(LobbyGameDealt),
+
+    #[serde(rename = "game_phase_changed")]
+    GamePhaseChanged(LobbyGamePhaseChanged),
+
+    #[serde(rename = "game_player_departed")]
+    GamePlayerDeparted(LobbyGamePlayerDeparted),
+
+    #[serde(rename = "game_started")]
+    GameStarted(LobbyGameStarted),
+
+    #[serde(rename = "guess")]
+    Guess(LobbyGuess),
+
+    #[serde(rename = "host_changed")]
+    HostChanged(LobbyHostChanged),
+
+    #[serde(rename = "leave")]
+    Leave(LobbyLeave),
+
+    #[serde(rename = "match_finished")]
+    MatchFinished(LobbyMatchFinished),
+
+    #[serde(rename = "phase_deadline")]
+    PhaseDeadline(LobbyPhaseDeadline),
+
+    #[serde(rename = "ready")]
+    Ready(LobbyReady),
+
+    #[serde(rename = "presence_changed")]
+    PresenceChanged(LobbyPresenceChanged),
+
+    #[serde(rename = "ready_changed")]
+    ReadyChanged(LobbyReadyChanged),
+
+    #[serde(rename = "resync")]
+    Resync(LobbyResync),
+
+    #[serde(rename = "round_scored")]
+    RoundScored(LobbyRoundScored),
+
+    #[serde(rename = "start_game")]
+    StartGame(LobbyStartGame),
+
+    #[serde(rename = "submit_clue")]
+    SubmitClue(LobbySubmitClue),
+
     #[serde(rename = "user_joined")]
     UserJoined(LobbyUserJoined),
 
+    #[serde(rename = "user_kicked")]
+    UserKicked(LobbyUserKicked),
+
     #[serde(rename = "user_left")]
     UserLeft(LobbyUserLeft),
+
+    #[serde(rename = "vote")]
+    Vote(LobbyVote),
+
+    #[serde(rename = "vote_cast")]
+    VoteCast(LobbyVoteCast),
+
+    #[serde(rename = "votes_revealed")]
+    VotesRevealed(LobbyVotesRevealed),
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LobbyAuthenticate {
     #[serde(rename = "local_id", skip_serializing_if = "Option::is_none")]
     pub local_id: Option<String>,
+
+    /// Sequence number of the last frame received, the frames after it are replayed.
+    #[serde(rename = "last_seen_seq", skip_serializing_if = "Option::is_none")]
+    pub last_seen_seq: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LobbyChatMessage {
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
     #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
 
@@ -35,21 +112,257 @@ pub struct LobbyChatMessage {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyClueSubmitted {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "clue", skip_serializing_if = "Option::is_none")]
+    pub clue: Option<String>,
+}
+
+/// Sent by the host to break a tied vote.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyDecideTie {
+    #[serde(rename = "accused", skip_serializing_if = "Option::is_none")]
+    pub accused: Option<String>,
+}
+
+/// Role dealt to a single player.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyGameDealt {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "chameleon", skip_serializing_if = "Option::is_none")]
+    pub chameleon: Option<bool>,
+
+    #[serde(rename = "secret_word", skip_serializing_if = "Option::is_none")]
+    pub secret_word: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyGamePhaseChanged {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "phase", skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+}
+
+/// Player who left the lobby during the game.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyGamePlayerDeparted {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyGameStarted {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "topic", skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+
+    #[serde(rename = "words", skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<String>>,
+}
+
+/// Sent by the chameleon.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyGuess {
+    #[serde(rename = "word", skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyHostChanged {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Sent by a member, the connection is closed afterwards.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LobbyLeave {}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyMatchFinished {
+    #[serde(rename = "match_id", skip_serializing_if = "Option::is_none")]
+    pub match_id: Option<String>,
+
+    /// Final standings, highest score first.
+    #[serde(rename = "standings", skip_serializing_if = "Option::is_none")]
+    pub standings: Option<Vec<LobbyMatchStanding>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyMatchStanding {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "score", skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+}
+
+/// Deadline of the current clue turn or vote.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyPhaseDeadline {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "phase", skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+
+    /// Player whose clue turn it is.
+    #[serde(rename = "turn", skip_serializing_if = "Option::is_none")]
+    pub turn: Option<String>,
+
+    /// RFC 3339.
+    #[serde(rename = "deadline", skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<String>,
+}
+
+/// Sent by a member to toggle being ready.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LobbyReady {}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyPresenceChanged {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "online", skip_serializing_if = "Option::is_none")]
+    pub online: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyReadyChanged {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "ready", skip_serializing_if = "Option::is_none")]
+    pub ready: Option<bool>,
+}
+
+/// Sent instead of the replay when the missed frames are no longer logged, the client reloads
+/// the resources.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyResync {
+    /// Sequence number the live frames continue after.
+    #[serde(rename = "seq", skip_serializing_if = "Option::is_none")]
+    pub seq: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyRoundScored {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "outcome", skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+
+    /// user id, points scored this round
+    #[serde(rename = "points", skip_serializing_if = "Option::is_none")]
+    pub points: Option<HashMap<String, u32>>,
+}
+
+/// Sent by the host.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LobbyStartGame {}
+
+/// Sent by the player whose turn it is.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbySubmitClue {
+    #[serde(rename = "clue", skip_serializing_if = "Option::is_none")]
+    pub clue: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LobbyUserJoined {
     #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
 }
 
+/// Sent to every member, the kicked member's connection is closed afterwards.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyUserKicked {
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct LobbyUserLeft {
     #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
 }
 
+/// Sent by a player.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyVote {
+    #[serde(rename = "accused", skip_serializing_if = "Option::is_none")]
+    pub accused: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyVoteCast {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    #[serde(rename = "user_id", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LobbyVotesRevealed {
+    #[serde(rename = "game_id", skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<String>,
+
+    /// voter user id, accused user id
+    #[serde(rename = "votes", skip_serializing_if = "Option::is_none")]
+    pub votes: Option<HashMap<String, String>>,
+
+    #[serde(rename = "accused", skip_serializing_if = "Option::is_none")]
+    pub accused: Option<String>,
+
+    #[serde(rename = "tied", skip_serializing_if = "Option::is_none")]
+    pub tied: Option<Vec<String>>,
+}
+
+/// Replies to requests by id, the requests sent by a client reply `true` once applied.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "method", content = "value")]
 pub enum LobbyResponse {
     #[serde(rename = "authenticate")]
     Authenticate(bool),
+
+    #[serde(rename = "chat_message")]
+    ChatMessage(bool),
+
+    #[serde(rename = "decide_tie")]
+    DecideTie(bool),
+
+    #[serde(rename = "guess")]
+    Guess(bool),
+
+    #[serde(rename = "leave")]
+    Leave(bool),
+
+    #[serde(rename = "ready")]
+    Ready(bool),
+
+    #[serde(rename = "start_game")]
+    StartGame(bool),
+
+    #[serde(rename = "submit_clue")]
+    SubmitClue(bool),
+
+    #[serde(rename = "vote")]
+    Vote(bool),
 }
@@ -35,6 +35,13 @@ pub struct Pagination {
     pub size: Option<String>,
 }
 
+/// Comma-separated sort fields, descending if prefixed with `-`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Sort {
+    #[serde(rename = "sort")]
+    pub sort: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Resource<T> {
     #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
@@ -305,6 +312,62 @@ impl ResourceIdentifiersDocument {
             links: None,
         }
     }
+
+    /// Try get resource identifier array.
+    ///
+    /// Convenience method.
+    pub fn try_get_collection(
+        &self,
+        display: &str,
+    ) -> Result<&Vec<ResourceIdentifier>, Box<Error>> {
+        match self.try_get_resource_identifiers()? {
+            ResourceIdentifiers::Collection(resources) => Ok(resources),
+            ResourceIdentifiers::Individual(_) => Err(Box::new(Error {
+                status: 422,
+                source: Some(Source {
+                    header: None,
+                    parameter: None,
+                    pointer: Some("/data".to_string()),
+                }),
+                title: Some("Invalid Member".to_string()),
+                detail: Some(format!("{display} must be a resource identifier array")),
+            })),
+        }
+    }
+
+    /// Try get individual resource identifier.
+    ///
+    /// Convenience method.
+    pub fn try_get_individual(&self, display: &str) -> Result<&ResourceIdentifier, Box<Error>> {
+        match self.try_get_resource_identifiers()? {
+            ResourceIdentifiers::Collection(_) => Err(Box::new(Error {
+                status: 422,
+                source: Some(Source {
+                    header: None,
+                    parameter: None,
+                    pointer: Some("/data".to_string()),
+                }),
+                title: Some("Invalid Member".to_string()),
+                detail: Some(format!("{display} must be a resource identifier object")),
+            })),
+            ResourceIdentifiers::Individual(resource) => Ok(resource),
+        }
+    }
+
+    pub fn try_get_resource_identifiers(&self) -> Result<&ResourceIdentifiers, Box<Error>> {
+        self.data.as_ref().ok_or_else(|| {
+            Box::new(Error {
+                status: 422,
+                source: Some(Source {
+                    header: None,
+                    parameter: None,
+                    pointer: Some("/data".to_string()),
+                }),
+                title: Some("Invalid Member".to_string()),
+                detail: Some("Data must be present".to_string()),
+            })
+        })
+    }
 }
 
 impl<T> Resources<T> {
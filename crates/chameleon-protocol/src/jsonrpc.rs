@@ -1,4 +1,7 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, value::MapDeserializer},
+    Deserialize, Serialize,
+};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Error {
@@ -31,6 +34,10 @@ pub struct RequestFrame<T> {
     #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
     pub id: Option<i64>,
 
+    /// Position in the stream of frames sent by the server, not part of JSON-RPC.
+    #[serde(rename = "seq", skip_serializing_if = "Option::is_none")]
+    pub seq: Option<i64>,
+
     #[serde(flatten)]
     pub data: T,
 }
@@ -71,7 +78,22 @@ impl<T, U> Frame<T, U> {
     pub fn new_request(id: Option<i64>, request: T) -> Frame<T, U> {
         Frame {
             jsonrpc: "2.0".to_string(),
-            type_: FrameType::Request(RequestFrame { id, data: request }),
+            type_: FrameType::Request(RequestFrame {
+                id,
+                seq: None,
+                data: request,
+            }),
+        }
+    }
+
+    pub fn new_sequenced_request(seq: i64, request: T) -> Frame<T, U> {
+        Frame {
+            jsonrpc: "2.0".to_string(),
+            type_: FrameType::Request(RequestFrame {
+                id: None,
+                seq: Some(seq),
+                data: request,
+            }),
         }
     }
 
@@ -176,3 +198,38 @@ where
         serde_json::to_string(self)
     }
 }
+
+/// Methods of a request enum tagged by `method`, as listed by its derived `Deserialize`.
+pub fn methods<'de, T>() -> &'static [&'static str]
+where
+    T: Deserialize<'de>,
+{
+    // an empty method is unknown to every enum, the error lists the known ones
+    let map = MapDeserializer::<_, Methods>::new(std::iter::once(("method", "")));
+    match T::deserialize(map) {
+        Ok(_) => &[],
+        Err(Methods(methods)) => methods,
+    }
+}
+
+/// Deserialization error keeping only the variants expected by `unknown_variant`.
+#[derive(Debug)]
+struct Methods(&'static [&'static str]);
+
+impl de::Error for Methods {
+    fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+        Self(&[])
+    }
+
+    fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
+        Self(expected)
+    }
+}
+
+impl std::fmt::Display for Methods {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected one of {:?}", self.0)
+    }
+}
+
+impl std::error::Error for Methods {}
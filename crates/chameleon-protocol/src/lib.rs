@@ -3,3 +3,4 @@ pub mod frames;
 pub mod jsonapi;
 pub mod jsonrpc;
 pub mod openid_connect;
+pub mod topic;
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of rows and columns of a topic card.
+pub const GRID_SIZE: usize = 4;
+
+/// Number of words on a topic card.
+pub const WORD_COUNT: usize = GRID_SIZE * GRID_SIZE;
+
+/// Topic card: a title and a 4x4 grid of words.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Topic {
+    #[serde(rename = "title")]
+    pub title: String,
+
+    #[serde(rename = "grid")]
+    pub grid: [[String; GRID_SIZE]; GRID_SIZE],
+}
+
+impl Topic {
+    /// Create a topic from words in row-major order.
+    ///
+    /// `None` unless there are exactly 16 words.
+    pub fn new(title: &str, words: &[String]) -> Option<Self> {
+        if words.len() != WORD_COUNT {
+            return None;
+        }
+
+        Some(Self {
+            title: title.to_string(),
+            grid: std::array::from_fn(|row| {
+                std::array::from_fn(|column| words[row * GRID_SIZE + column].clone())
+            }),
+        })
+    }
+
+    /// Get word by its row-major index.
+    pub fn word(&self, index: usize) -> Option<&str> {
+        self.grid
+            .get(index / GRID_SIZE)
+            .and_then(|row| row.get(index % GRID_SIZE))
+            .map(String::as_str)
+    }
+
+    /// Words in row-major order.
+    pub fn words(&self) -> impl Iterator<Item = &String> {
+        self.grid.iter().flatten()
+    }
+}
+
+/// Get the coordinate of a row-major index, columns are lettered A to D and rows numbered 1 to 4.
+pub fn coordinate(index: usize) -> Option<String> {
+    (index < WORD_COUNT).then(|| {
+        let column = char::from(b'A' + u8::try_from(index % GRID_SIZE).unwrap());
+        format!("{column}{}", index / GRID_SIZE + 1)
+    })
+}
+
+/// Bundled default deck.
+pub fn default_deck() -> Vec<Topic> {
+    DEFAULT_DECK
+        .iter()
+        .map(|(title, words)| Topic {
+            title: (*title).to_string(),
+            grid: std::array::from_fn(|row| {
+                std::array::from_fn(|column| words[row * GRID_SIZE + column].to_string())
+            }),
+        })
+        .collect()
+}
+
+const DEFAULT_DECK: &[(&str, [&str; WORD_COUNT])] = &[
+    (
+        "Animals",
+        [
+            "Bear",
+            "Cat",
+            "Cow",
+            "Crocodile",
+            "Dog",
+            "Elephant",
+            "Fox",
+            "Giraffe",
+            "Horse",
+            "Lion",
+            "Monkey",
+            "Owl",
+            "Penguin",
+            "Rabbit",
+            "Shark",
+            "Snake",
+        ],
+    ),
+    (
+        "Food",
+        [
+            "Burger",
+            "Cake",
+            "Cheese",
+            "Chips",
+            "Chocolate",
+            "Curry",
+            "Eggs",
+            "Ice Cream",
+            "Noodles",
+            "Pancakes",
+            "Pasta",
+            "Pizza",
+            "Salad",
+            "Sandwich",
+            "Soup",
+            "Sushi",
+        ],
+    ),
+    (
+        "Sports",
+        [
+            "Athletics",
+            "Baseball",
+            "Basketball",
+            "Boxing",
+            "Cricket",
+            "Cycling",
+            "Football",
+            "Golf",
+            "Gymnastics",
+            "Hockey",
+            "Rowing",
+            "Rugby",
+            "Skiing",
+            "Swimming",
+            "Tennis",
+            "Volleyball",
+        ],
+    ),
+    (
+        "Countries",
+        [
+            "Australia",
+            "Brazil",
+            "Canada",
+            "China",
+            "Egypt",
+            "France",
+            "Germany",
+            "India",
+            "Italy",
+            "Japan",
+            "Mexico",
+            "Norway",
+            "Russia",
+            "Spain",
+            "United Kingdom",
+            "United States",
+        ],
+    ),
+    (
+        "Jobs",
+        [
+            "Actor",
+            "Baker",
+            "Builder",
+            "Chef",
+            "Dentist",
+            "Doctor",
+            "Farmer",
+            "Firefighter",
+            "Lawyer",
+            "Mechanic",
+            "Nurse",
+            "Pilot",
+            "Plumber",
+            "Police Officer",
+            "Scientist",
+            "Teacher",
+        ],
+    ),
+    (
+        "Transport",
+        [
+            "Aeroplane",
+            "Ambulance",
+            "Bicycle",
+            "Boat",
+            "Bus",
+            "Car",
+            "Helicopter",
+            "Hot Air Balloon",
+            "Lorry",
+            "Motorbike",
+            "Rocket",
+            "Scooter",
+            "Submarine",
+            "Taxi",
+            "Tractor",
+            "Train",
+        ],
+    ),
+    (
+        "Weather",
+        [
+            "Blizzard",
+            "Cloud",
+            "Drizzle",
+            "Fog",
+            "Frost",
+            "Hail",
+            "Heatwave",
+            "Hurricane",
+            "Lightning",
+            "Rain",
+            "Rainbow",
+            "Snow",
+            "Sunshine",
+            "Thunder",
+            "Tornado",
+            "Wind",
+        ],
+    ),
+    (
+        "Musical Instruments",
+        [
+            "Bagpipes",
+            "Banjo",
+            "Cello",
+            "Clarinet",
+            "Drums",
+            "Flute",
+            "Guitar",
+            "Harmonica",
+            "Harp",
+            "Piano",
+            "Recorder",
+            "Saxophone",
+            "Triangle",
+            "Trombone",
+            "Trumpet",
+            "Violin",
+        ],
+    ),
+];